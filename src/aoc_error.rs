@@ -1,26 +1,111 @@
-use std::{fmt, io};
+use std::{error, fmt, io};
+
+use crate::year2019::intcode_computer::IntcodeComputerError;
 
 #[derive(Debug)]
-pub struct AocError {
-    message: String,
+pub enum AocError {
+    /// The input file of the day could not be read.
+    InputMissing { day: u32, source: io::Error },
+    Io(io::Error),
+    /// The input is malformed. Line and column are 1-based.
+    Parse { message: String, line: Option<usize>, column: Option<usize> },
+    /// The solution failed on a valid input.
+    Puzzle(String),
+    Intcode(IntcodeComputerError),
+    /// The star or the whole day (`star` is `None`) has no solution yet.
+    NotImplemented { day: u32, star: Option<u32> },
+    /// Invalid command line argument.
+    InvalidArgument(String),
 }
 
 pub type AocResult = Result<String, AocError>;
 
 impl AocError {
+    /// Error in the puzzle logic.
     pub fn new(message: String) -> AocError {
-        AocError { message }
+        AocError::Puzzle(message)
+    }
+
+    pub fn parse(message: String) -> AocError {
+        AocError::Parse { message, line: None, column: None }
+    }
+
+    pub fn parse_at(message: String, line: usize, column: Option<usize>) -> AocError {
+        AocError::Parse { message, line: Some(line), column }
     }
 }
 
 impl fmt::Display for AocError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            AocError::InputMissing { day, source } => write!(f, "Missing input for day {}: {}", day, source),
+            AocError::Io(error) => write!(f, "IO error: {}", error),
+            AocError::Parse { message, line: Some(line), column: Some(column) } =>
+                write!(f, "Parse error on line {}, column {}: {}", line, column, message),
+            AocError::Parse { message, line: Some(line), column: None } =>
+                write!(f, "Parse error on line {}: {}", line, message),
+            AocError::Parse { message, .. } => write!(f, "Parse error: {}", message),
+            AocError::Puzzle(message) => write!(f, "{}", message),
+            AocError::Intcode(error) => write!(f, "Intcode Computer: {}", error),
+            AocError::NotImplemented { day, star: Some(star) } => write!(f, "Day {} star {} not implemented", day, star),
+            AocError::NotImplemented { day, star: None } => write!(f, "Day {} not implemented", day),
+            AocError::InvalidArgument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Clone for AocError {
+    fn clone(&self) -> Self {
+        let clone_io_error = |error: &io::Error| io::Error::new(error.kind(), error.to_string());
+        match self {
+            AocError::InputMissing { day, source } => AocError::InputMissing { day: *day, source: clone_io_error(source) },
+            AocError::Io(error) => AocError::Io(clone_io_error(error)),
+            AocError::Parse { message, line, column } =>
+                AocError::Parse { message: message.clone(), line: *line, column: *column },
+            AocError::Puzzle(message) => AocError::Puzzle(message.clone()),
+            AocError::Intcode(error) => AocError::Intcode(error.clone()),
+            AocError::NotImplemented { day, star } => AocError::NotImplemented { day: *day, star: *star },
+            AocError::InvalidArgument(message) => AocError::InvalidArgument(message.clone()),
+        }
+    }
+}
+
+impl error::Error for AocError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AocError::InputMissing { source, .. } => Some(source),
+            AocError::Io(error) => Some(error),
+            AocError::Intcode(error) => Some(error),
+            _ => None,
+        }
     }
 }
 
 impl From<io::Error> for AocError {
     fn from(error: io::Error) -> Self {
-        AocError { message: format!("IO error: {}", error.to_string()) }
+        AocError::Io(error)
+    }
+}
+
+impl From<IntcodeComputerError> for AocError {
+    fn from(error: IntcodeComputerError) -> Self {
+        AocError::Intcode(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn test_error_source() {
+        let error = AocError::from(IntcodeComputerError::OutOfInputs);
+        assert!(matches!(error, AocError::Intcode(IntcodeComputerError::OutOfInputs)));
+        assert!(error.source().is_some());
+        assert_eq!(AocError::parse_at(String::from("Invalid digit"), 3, Some(7)).to_string(),
+                   "Parse error on line 3, column 7: Invalid digit");
+        assert!(AocError::NotImplemented { day: 13, star: Some(2) }.source().is_none());
     }
 }
@@ -0,0 +1,176 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_framework::aoc_error::AocError;
+use aoc_framework::interface::{AdventOfCodeCalendar, StarRun};
+use aoc_framework::solution::ParameterValues;
+
+use crate::cli::{ReportFormat, StarSelection};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::PartOne => "part_one",
+            Phase::PartTwo => "part_two",
+        }
+    }
+}
+
+/// Wall time statistics of a single phase of a day over all the runs.
+#[derive(Debug, PartialEq)]
+pub struct PhaseTimes {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl PhaseTimes {
+    /// Returns `None` when there are no `samples`.
+    fn from_samples(year: u32, day: u32, phase: Phase, mut samples: Vec<Duration>) -> Option<PhaseTimes> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let middle = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+        Some(PhaseTimes { year, day, phase, runs: samples.len(), min: samples[0], median, max: samples[samples.len() - 1] })
+    }
+}
+
+/// Runs the `day` `runs` times and collects times of its phases. Parts which are not implemented are left out, and so
+/// is the whole day when none of its selected parts is. Any other error stops the benchmark of the day.
+pub fn benchmark_day(calendar: &dyn AdventOfCodeCalendar, day: u32, input: Option<&str>, parameters: &ParameterValues,
+                     runs: u32, stars: StarSelection) -> Result<Vec<PhaseTimes>, AocError> {
+    let mut parse_samples = vec![];
+    let mut part_one_samples = vec![];
+    let mut part_two_samples = vec![];
+
+    for _ in 0..runs {
+        let run = calendar.run_day_with(day, input, parameters);
+        parse_samples.push(run.parse_time);
+        if stars.includes_first() {
+            add_sample(&mut part_one_samples, run.first_star)?;
+        }
+        if stars.includes_second() {
+            add_sample(&mut part_two_samples, run.second_star)?;
+        }
+    }
+
+    // The parse time of a day which is not implemented is not a measurement.
+    if part_one_samples.is_empty() && part_two_samples.is_empty() {
+        return Ok(vec![]);
+    }
+    let year = calendar.year();
+    Ok(vec![(Phase::Parse, parse_samples), (Phase::PartOne, part_one_samples), (Phase::PartTwo, part_two_samples)]
+        .into_iter()
+        .filter_map(|(phase, samples)| PhaseTimes::from_samples(year, day, phase, samples))
+        .collect())
+}
+
+fn add_sample(samples: &mut Vec<Duration>, star: StarRun) -> Result<(), AocError> {
+    match star.result {
+        Ok(_) => samples.push(star.time),
+        Err(AocError::NotImplemented { .. }) => (),
+        Err(error) => return Err(error),
+    }
+    Ok(())
+}
+
+pub fn format_report(times: &[PhaseTimes], format: ReportFormat) -> String {
+    let mut report = String::new();
+    match format {
+        ReportFormat::Text => {
+            writeln!(report, "{:>4} {:>3} {:<8} {:>6} {:>14} {:>14} {:>14}", "Year", "Day", "Phase", "Runs", "Min", "Median", "Max").unwrap();
+            for time in times {
+                writeln!(report, "{:>4} {:>3} {:<8} {:>6} {:>14} {:>14} {:>14}", time.year, time.day, time.phase.name(), time.runs,
+                         format!("{:.2?}", time.min), format!("{:.2?}", time.median), format!("{:.2?}", time.max)).unwrap();
+            }
+        },
+        ReportFormat::Json => {
+            let entries = times.iter().map(|time| format!(
+                "  {{\"year\": {}, \"day\": {}, \"phase\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+                time.year, time.day, time.phase.name(), time.runs, time.min.as_nanos(), time.median.as_nanos(),
+                time.max.as_nanos())).collect::<Vec<String>>();
+            match entries.is_empty() {
+                true => writeln!(report, "[]").unwrap(),
+                false => writeln!(report, "[\n{}\n]", entries.join(",\n")).unwrap(),
+            }
+        },
+        ReportFormat::Csv => {
+            writeln!(report, "year,day,phase,runs,min_ns,median_ns,max_ns").unwrap();
+            for time in times {
+                writeln!(report, "{},{},{},{},{},{},{}", time.year, time.day, time.phase.name(), time.runs,
+                         time.min.as_nanos(), time.median.as_nanos(), time.max.as_nanos()).unwrap();
+            }
+        },
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_framework::interface::DayRun;
+
+    use super::*;
+
+    /// Calendar with only the first star of day 1.
+    struct FirstStarCalendar;
+
+    impl AdventOfCodeCalendar for FirstStarCalendar {
+        fn year(&self) -> u32 {
+            2019
+        }
+
+        fn run_day_with(&self, day: u32, _input: Option<&str>, _parameters: &ParameterValues) -> DayRun {
+            match day {
+                1 => DayRun {
+                    parse_time: Duration::from_millis(1),
+                    first_star: StarRun { result: Ok(String::from("42")), time: Duration::from_millis(2) },
+                    second_star: StarRun { result: Err(AocError::NotImplemented { day, star: Some(2) }),
+                                           time: Duration::ZERO },
+                },
+                _ => DayRun::failed(AocError::NotImplemented { day, star: None }, Duration::ZERO),
+            }
+        }
+
+        fn implemented_days(&self) -> Vec<u32> {
+            vec![1]
+        }
+    }
+
+    #[test]
+    fn test_not_implemented() {
+        let parameters = ParameterValues::new();
+        let times = benchmark_day(&FirstStarCalendar, 1, None, &parameters, 3, StarSelection::Both).unwrap();
+        assert_eq!(times.iter().map(|time| time.phase).collect::<Vec<Phase>>(), vec![Phase::Parse, Phase::PartOne]);
+        assert!(benchmark_day(&FirstStarCalendar, 1, None, &parameters, 3, StarSelection::Second).unwrap().is_empty());
+        assert!(benchmark_day(&FirstStarCalendar, 2, None, &parameters, 3, StarSelection::Both).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_phase_times() {
+        let samples = [5, 1, 3, 2].iter().map(|millis| Duration::from_millis(*millis)).collect();
+        let times = PhaseTimes::from_samples(2019, 3, Phase::Parse, samples).unwrap();
+        assert_eq!((times.min, times.median, times.max),
+                   (Duration::from_millis(1), Duration::from_micros(2500), Duration::from_millis(5)));
+        assert!(PhaseTimes::from_samples(2019, 3, Phase::Parse, vec![]).is_none());
+
+        assert_eq!(format_report(&[times], ReportFormat::Csv),
+                   "year,day,phase,runs,min_ns,median_ns,max_ns\n2019,3,parse,4,1000000,2500000,5000000\n");
+    }
+}
@@ -0,0 +1,231 @@
+use std::any::Any;
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use aoc_framework::aoc_error::AocError;
+use aoc_framework::interface::{AdventOfCodeCalendar, StarRun};
+use aoc_framework::solution::ParameterValues;
+use aoc_framework::utils::file_handling::{self, Verification};
+
+use crate::cli::StarSelection;
+
+#[derive(Debug, PartialEq)]
+pub enum StarStatus {
+    /// The star has an answer, which matches the expected one if it is known.
+    Ok(String),
+    Wrong { answer: String, expected: String },
+    Error(String),
+    NotImplemented,
+    /// The star was not selected.
+    Skipped,
+}
+
+impl StarStatus {
+    fn new(star: &StarRun, is_selected: bool, expected: Option<&String>) -> StarStatus {
+        if !is_selected {
+            return StarStatus::Skipped;
+        }
+        match &star.result {
+            Ok(answer) => match file_handling::verify_answer(answer, expected.map(String::as_str)) {
+                Verification::Incorrect { expected } => StarStatus::Wrong { answer: answer.clone(), expected },
+                Verification::Correct | Verification::Unknown => StarStatus::Ok(answer.clone()),
+            },
+            Err(AocError::NotImplemented { .. }) => StarStatus::NotImplemented,
+            Err(error) => StarStatus::Error(error.to_string()),
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self, StarStatus::Wrong { .. } | StarStatus::Error(_))
+    }
+
+    /// Content of the star column of the table.
+    fn cell(&self) -> String {
+        match self {
+            StarStatus::Ok(answer) | StarStatus::Wrong { answer, .. } => match answer.lines().count() {
+                0 | 1 => answer.clone(),
+                lines => format!("<{} lines>", lines),
+            },
+            StarStatus::Error(_) => String::from("error"),
+            StarStatus::NotImplemented | StarStatus::Skipped => String::from("-"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DaySummary {
+    pub day: u32,
+    pub stars: [StarStatus; 2],
+    /// Parse time together with the time of the selected stars.
+    pub time: Duration,
+}
+
+impl DaySummary {
+    pub fn is_failure(&self) -> bool {
+        self.stars.iter().any(StarStatus::is_failure)
+    }
+
+    fn status(&self) -> String {
+        let problems = self.stars.iter().enumerate().filter_map(|(index, star)| match star {
+            StarStatus::Wrong { .. } => Some(format!("wrong star {}", index + 1)),
+            StarStatus::Error(_) => Some(format!("error star {}", index + 1)),
+            StarStatus::NotImplemented => Some(format!("star {} not implemented", index + 1)),
+            _ => None,
+        }).collect::<Vec<String>>();
+
+        match problems.is_empty() {
+            true => String::from("ok"),
+            false => problems.join(", "),
+        }
+    }
+}
+
+/// Runs the `days` on at most `jobs` threads at the same time. Expected answers are only checked for the default
+/// input and parameters. A day which panics is reported as an error of its selected stars and doesn't stop the
+/// others. The summaries are in the order of the `days`.
+pub fn check_days(calendar: &dyn AdventOfCodeCalendar, days: &[u32], input: Option<&str>, parameters: &ParameterValues,
+                  stars: StarSelection, jobs: usize) -> Vec<DaySummary> {
+    let next_day = AtomicUsize::new(0);
+    let summaries = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            scope.spawn(|| {
+                while let Some(day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    let summary = panic::catch_unwind(AssertUnwindSafe(|| check_day(calendar, *day, input, parameters, stars)))
+                        .unwrap_or_else(|payload| panicked_day(*day, stars, payload));
+                    summaries.lock().unwrap().push(summary);
+                }
+            });
+        }
+    });
+
+    let mut summaries = summaries.into_inner().unwrap();
+    summaries.sort_by_key(|summary| days.iter().position(|day| *day == summary.day));
+    summaries
+}
+
+fn check_day(calendar: &dyn AdventOfCodeCalendar, day: u32, input: Option<&str>, parameters: &ParameterValues,
+             stars: StarSelection) -> DaySummary {
+    let run = calendar.run_day_with(day, input, parameters);
+    let (first_expected, second_expected) = match input.is_none() && parameters.is_empty() {
+        true => calendar.expected_answers(day),
+        false => (None, None),
+    };
+
+    let mut time = run.parse_time;
+    if stars.includes_first() {
+        time += run.first_star.time;
+    }
+    if stars.includes_second() {
+        time += run.second_star.time;
+    }
+
+    DaySummary {
+        day,
+        stars: [
+            StarStatus::new(&run.first_star, stars.includes_first(), first_expected.as_ref()),
+            StarStatus::new(&run.second_star, stars.includes_second(), second_expected.as_ref()),
+        ],
+        time,
+    }
+}
+
+fn panicked_day(day: u32, stars: StarSelection, payload: Box<dyn Any + Send>) -> DaySummary {
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| String::from("unknown cause")),
+    };
+    let status = |is_selected| match is_selected {
+        true => StarStatus::Error(format!("panicked: {}", message)),
+        false => StarStatus::Skipped,
+    };
+    DaySummary { day, stars: [status(stars.includes_first()), status(stars.includes_second())], time: Duration::ZERO }
+}
+
+/// Table with a line per day, followed by the details of the wrong answers and errors.
+pub fn format_summary(summaries: &[DaySummary]) -> String {
+    let star_width = summaries.iter()
+        .flat_map(|summary| summary.stars.iter().map(|star| star.cell().chars().count()))
+        .chain(std::iter::once("Star 1".len()))
+        .max()
+        .unwrap();
+
+    let mut table = String::new();
+    writeln!(table, "{:>3}  {:<width$}  {:<width$}  {:>10}  Status", "Day", "Star 1", "Star 2", "Time", width = star_width).unwrap();
+    for summary in summaries {
+        writeln!(table, "{:>3}  {:<width$}  {:<width$}  {:>10}  {}", summary.day, summary.stars[0].cell(),
+                 summary.stars[1].cell(), format!("{:.2?}", summary.time), summary.status(), width = star_width).unwrap();
+    }
+
+    for summary in summaries {
+        for (index, star) in summary.stars.iter().enumerate() {
+            match star {
+                StarStatus::Wrong { answer, expected } =>
+                    writeln!(table, "Day {} star {}: expected {}, got {}", summary.day, index + 1, expected, answer).unwrap(),
+                StarStatus::Error(error) => writeln!(table, "Day {} star {}: error: {}", summary.day, index + 1, error).unwrap(),
+                _ => (),
+            }
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_framework::interface::DayRun;
+
+    use super::*;
+
+    /// Calendar whose day 2 panics.
+    struct PanickingCalendar;
+
+    impl AdventOfCodeCalendar for PanickingCalendar {
+        fn year(&self) -> u32 {
+            2019
+        }
+
+        fn run_day_with(&self, day: u32, _input: Option<&str>, _parameters: &ParameterValues) -> DayRun {
+            if day == 2 {
+                panic!("no entry found for key");
+            }
+            let star = || StarRun { result: Ok(day.to_string()), time: Duration::ZERO };
+            DayRun { parse_time: Duration::ZERO, first_star: star(), second_star: star() }
+        }
+
+        fn implemented_days(&self) -> Vec<u32> {
+            vec![1, 2, 3]
+        }
+    }
+
+    #[test]
+    fn test_panicking_day() {
+        let summaries = check_days(&PanickingCalendar, &[1, 2, 3], None, &ParameterValues::new(), StarSelection::First, 2);
+        assert_eq!(summaries.iter().map(|summary| summary.day).collect::<Vec<u32>>(), vec![1, 2, 3]);
+        assert!(!summaries[0].is_failure() && !summaries[2].is_failure());
+        assert_eq!(summaries[1].stars, [StarStatus::Error(String::from("panicked: no entry found for key")),
+                                        StarStatus::Skipped]);
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let summaries = [
+            DaySummary { day: 1, stars: [StarStatus::Ok(String::from("42")), StarStatus::Ok(String::from("#.\n.#"))],
+                         time: Duration::from_millis(3) },
+            DaySummary { day: 2, stars: [StarStatus::Wrong { answer: String::from("7"), expected: String::from("8") },
+                                         StarStatus::NotImplemented], time: Duration::from_millis(12) },
+        ];
+        assert!(!summaries[0].is_failure());
+        assert!(summaries[1].is_failure());
+        assert_eq!(format_summary(&summaries), "\
+Day  Star 1     Star 2           Time  Status
+  1  42         <2 lines>      3.00ms  ok
+  2  7          -             12.00ms  wrong star 1, star 2 not implemented
+Day 2 star 1: expected 8, got 7
+");
+    }
+}
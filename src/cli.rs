@@ -0,0 +1,383 @@
+use std::path::PathBuf;
+
+use aoc_framework::aoc_error::AocError;
+use aoc_framework::solution::ParameterValues;
+
+const LAST_DAY: u32 = 25;
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    Single(u32),
+    Range(u32, u32),
+    All,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StarSelection {
+    First,
+    Second,
+    Both,
+}
+
+impl StarSelection {
+    pub fn includes_first(self) -> bool {
+        self != StarSelection::Second
+    }
+
+    pub fn includes_second(self) -> bool {
+        self != StarSelection::First
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    /// Input file from the inputs folder, chosen by the calendar.
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    /// Latest registered year when not given.
+    pub year: Option<u32>,
+    pub days: DaySelection,
+    pub stars: StarSelection,
+    pub input: InputSource,
+    /// Print the parse and solve time of every day.
+    pub show_times: bool,
+    /// Store the answers which have no expected answer yet as the expected ones.
+    pub record: bool,
+    /// Solver parameters overriding the puzzle defaults.
+    pub parameters: ParameterValues,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            year: None, days: DaySelection::All, stars: StarSelection::Both, input: InputSource::Default,
+            show_times: false, record: false, parameters: ParameterValues::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IntcodeTool {
+    /// Print an assembly listing of the program.
+    Disassemble,
+    /// Start an interactive debugger.
+    Debug,
+    /// Run the program and print every executed instruction.
+    Trace,
+    /// Run the program and print instruction counts.
+    Profile,
+    /// Play the arcade game of day 13 in the terminal.
+    Arcade,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct IntcodeOptions {
+    pub tool: IntcodeTool,
+    pub path: PathBuf,
+    /// Values queued as the program's input.
+    pub inputs: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchmarkOptions {
+    /// How many times each day is run.
+    pub runs: u32,
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CheckOptions {
+    /// Number of days run at the same time, the number of CPUs when not given.
+    pub jobs: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Benchmark(RunOptions, BenchmarkOptions),
+    /// Run the days in parallel and print a summary table.
+    Check(RunOptions, CheckOptions),
+    /// Run the examples from the manifests of the days.
+    Examples(RunOptions),
+    Intcode(IntcodeOptions),
+    Help,
+}
+
+pub fn usage() -> String {
+    String::from(
+"Usage: aoc_runner [OPTIONS]
+
+Options:
+    -y, --year <YEAR>        Calendar year to run (default: the latest one)
+    -d, --day <DAYS>         Day number, inclusive range such as 3-7, or 'all' (default: all)
+    -s, --star <STAR>        Star to run: 1, 2 or both (default: both)
+    -i, --input <PATH>       Input file to use instead of the default one, '-' reads stdin.
+                             Only allowed when a single day is selected
+    -p, --param <NAME=VALUE> Override a parameter of the solver, such as the number of simulated steps.
+                             Can be repeated, only allowed when a single day is selected
+    -t, --time               Print how long parsing the input and solving each star took
+    -r, --record             Store the answers of the stars without a known answer as the correct ones
+    -b, --bench <RUNS>       Run the selected days RUNS times and report min/median/max times
+    -f, --format <FORMAT>    Benchmark report format: text, json or csv (default: text)
+    -e, --examples           Run the examples listed in the manifests of the selected days
+    -c, --check              Run the selected days in parallel and print a summary table
+    -j, --jobs <JOBS>        Number of days run at the same time by --check (default: number of CPUs)
+    -h, --help               Print this message
+
+Intcode tools, working with the program in the file:
+    --disassemble <PATH>     Print an assembly listing of the program
+    --debug <PATH>           Debug the program interactively
+    --trace <PATH>           Run the program and print every executed instruction
+    --profile <PATH>         Run the program and print instruction counts and hot addresses
+    --arcade <PATH>          Play the arcade game of day 13 with the arrow keys, q quits
+    --program-input <VALUES> Comma separated values queued as the program's input")
+}
+
+pub fn parse_arguments(arguments: impl IntoIterator<Item = String>) -> Result<Command, AocError> {
+    let mut options = RunOptions::default();
+
+    let mut benchmark_runs = None;
+    let mut report_format = None;
+    let mut is_check = false;
+    let mut is_examples = false;
+    let mut jobs = None;
+    let mut intcode_tool = None;
+    let mut program_inputs = None;
+    // Options of the puzzle runs, which make no sense together with the Intcode tools.
+    let mut run_arguments = vec![];
+
+    let mut arguments = arguments.into_iter();
+    while let Some(argument) = arguments.next() {
+        let mut value_for = |option: &str| arguments.next()
+            .ok_or_else(|| AocError::InvalidArgument(format!("Missing value for {}", option)));
+        let tool = match argument.as_str() {
+            "--disassemble" => Some(IntcodeTool::Disassemble),
+            "--debug" => Some(IntcodeTool::Debug),
+            "--trace" => Some(IntcodeTool::Trace),
+            "--profile" => Some(IntcodeTool::Profile),
+            "--arcade" => Some(IntcodeTool::Arcade),
+            _ => None,
+        };
+        if let Some(tool) = tool {
+            if intcode_tool.is_some() {
+                return Err(AocError::InvalidArgument(String::from("Only one Intcode tool can be used at a time")));
+            }
+            intcode_tool = Some((tool, PathBuf::from(value_for(&argument)?)));
+            continue;
+        }
+        if !matches!(argument.as_str(), "-h" | "--help" | "--program-input") {
+            run_arguments.push(argument.clone());
+        }
+
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--program-input" => program_inputs = Some(parse_program_inputs(&value_for(&argument)?)?),
+            "-y" | "--year" => options.year = Some(parse_number(&value_for(&argument)?, "year")?),
+            "-d" | "--day" => options.days = parse_days(&value_for(&argument)?)?,
+            "-s" | "--star" => options.stars = parse_stars(&value_for(&argument)?)?,
+            "-t" | "--time" => options.show_times = true,
+            "-r" | "--record" => options.record = true,
+            "-b" | "--bench" => benchmark_runs = Some(parse_number(&value_for(&argument)?, "number of runs")?),
+            "-f" | "--format" => report_format = Some(parse_report_format(&value_for(&argument)?)?),
+            "-c" | "--check" => is_check = true,
+            "-e" | "--examples" => is_examples = true,
+            "-j" | "--jobs" => jobs = Some(parse_number(&value_for(&argument)?, "number of jobs")? as usize),
+            "-p" | "--param" => {
+                let (name, value) = parse_parameter(&value_for(&argument)?)?;
+                options.parameters.insert(name, value);
+            },
+            "-i" | "--input" => options.input = match value_for(&argument)?.as_str() {
+                "-" => InputSource::Stdin,
+                path => InputSource::File(PathBuf::from(path)),
+            },
+            unknown => return Err(AocError::InvalidArgument(format!("Unknown argument '{}'", unknown))),
+        }
+    }
+
+    match (intcode_tool, program_inputs) {
+        (Some(_), _) if !run_arguments.is_empty() => return Err(AocError::InvalidArgument(format!(
+            "{} can't be combined with an Intcode tool", run_arguments.join(", ")))),
+        (Some((tool, path)), inputs) => return Ok(Command::Intcode(IntcodeOptions {
+            tool, path, inputs: inputs.unwrap_or_default() })),
+        (None, Some(_)) => return Err(AocError::InvalidArgument(String::from(
+            "--program-input can only be used with an Intcode tool"))),
+        (None, None) => (),
+    }
+
+    let is_single_day = matches!(options.days, DaySelection::Single(_));
+    if options.input != InputSource::Default && !is_single_day {
+        return Err(AocError::InvalidArgument(String::from("An explicit input can only be used with a single day")));
+    }
+    if !options.parameters.is_empty() && !is_single_day {
+        return Err(AocError::InvalidArgument(String::from("Parameters can only be used with a single day")));
+    }
+
+    if options.record && (options.input != InputSource::Default || !options.parameters.is_empty() || is_check
+                          || benchmark_runs.is_some()) {
+        return Err(AocError::InvalidArgument(String::from("--record can only be used when running the default inputs")));
+    }
+    if is_examples {
+        let is_plain_run = options.input == InputSource::Default && options.parameters.is_empty() && !options.record;
+        return match is_plain_run && !is_check && benchmark_runs.is_none() {
+            true => Ok(Command::Examples(options)),
+            false => Err(AocError::InvalidArgument(String::from(
+                "--examples can't be combined with --input, --param, --record, --check or --bench"))),
+        };
+    }
+    if jobs == Some(0) {
+        return Err(AocError::InvalidArgument(String::from("Check needs at least one job")));
+    }
+    match (is_check, benchmark_runs.is_some(), jobs) {
+        (true, true, _) => return Err(AocError::InvalidArgument(String::from("--check can't be combined with --bench"))),
+        (true, false, jobs) => return Ok(Command::Check(options, CheckOptions { jobs })),
+        (false, _, Some(_)) => return Err(AocError::InvalidArgument(String::from("--jobs can only be used with --check"))),
+        (false, _, None) => (),
+    }
+
+    match (benchmark_runs, report_format) {
+        (Some(0), _) => Err(AocError::InvalidArgument(String::from("Benchmark needs at least one run"))),
+        (Some(runs), format) => Ok(Command::Benchmark(options, BenchmarkOptions {
+            runs, format: format.unwrap_or(ReportFormat::Text) })),
+        (None, Some(_)) => Err(AocError::InvalidArgument(String::from("--format can only be used with --bench"))),
+        (None, None) => Ok(Command::Run(options)),
+    }
+}
+
+/// Expands the day selection into a list of days, `All` is limited to the `implemented_days`.
+pub fn selected_days(selection: &DaySelection, implemented_days: &[u32]) -> Vec<u32> {
+    match selection {
+        DaySelection::Single(day) => vec![*day],
+        DaySelection::Range(first, last) => (*first..=*last).collect(),
+        DaySelection::All => implemented_days.to_vec(),
+    }
+}
+
+fn parse_days(value: &str) -> Result<DaySelection, AocError> {
+    if value == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    let (first, last) = match value.split_once('-') {
+        Some((first, last)) => (parse_number(first, "day")?, parse_number(last, "day")?),
+        None => {
+            let day = parse_number(value, "day")?;
+            (day, day)
+        },
+    };
+    if first == 0 || last > LAST_DAY || first > last {
+        return Err(AocError::InvalidArgument(format!("Invalid days '{}', expected days between 1 and {}", value, LAST_DAY)));
+    }
+
+    match value.contains('-') {
+        true => Ok(DaySelection::Range(first, last)),
+        false => Ok(DaySelection::Single(first)),
+    }
+}
+
+fn parse_stars(value: &str) -> Result<StarSelection, AocError> {
+    match value {
+        "1" => Ok(StarSelection::First),
+        "2" => Ok(StarSelection::Second),
+        "both" => Ok(StarSelection::Both),
+        unknown => Err(AocError::InvalidArgument(format!("Invalid star '{}', expected 1, 2 or both", unknown))),
+    }
+}
+
+fn parse_report_format(value: &str) -> Result<ReportFormat, AocError> {
+    match value {
+        "text" => Ok(ReportFormat::Text),
+        "json" => Ok(ReportFormat::Json),
+        "csv" => Ok(ReportFormat::Csv),
+        unknown => Err(AocError::InvalidArgument(format!("Invalid format '{}', expected text, json or csv", unknown))),
+    }
+}
+
+fn parse_parameter(value: &str) -> Result<(String, String), AocError> {
+    match value.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.trim().to_string())),
+        _ => Err(AocError::InvalidArgument(format!("Invalid parameter '{}', expected NAME=VALUE", value))),
+    }
+}
+
+fn parse_program_inputs(value: &str) -> Result<Vec<i64>, AocError> {
+    value.split(',').map(|input| input.trim().parse::<i64>()
+        .map_err(|err| AocError::InvalidArgument(format!("Invalid program input '{}': {}", input, err)))).collect()
+}
+
+fn parse_number(value: &str, name: &str) -> Result<u32, AocError> {
+    value.trim().parse::<u32>().map_err(|err| AocError::InvalidArgument(format!("Invalid {} '{}': {}", name, value, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &[&str]) -> Result<Command, AocError> {
+        parse_arguments(arguments.iter().map(|argument| argument.to_string()))
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        assert_eq!(parse(&[]).unwrap(), Command::Run(RunOptions::default()));
+        assert_eq!(parse(&["-d", "3-7", "--star", "2", "-y", "2019"]).unwrap(), Command::Run(RunOptions {
+            year: Some(2019), days: DaySelection::Range(3, 7), stars: StarSelection::Second, ..RunOptions::default() }));
+        assert_eq!(parse(&["--day", "5", "-i", "-", "-t"]).unwrap(), Command::Run(RunOptions {
+            days: DaySelection::Single(5), input: InputSource::Stdin, show_times: true, ..RunOptions::default() }));
+        assert_eq!(parse(&["-d", "5", "-h"]).unwrap(), Command::Help);
+        assert_eq!(parse(&["-d", "5", "-r"]).unwrap(), Command::Run(RunOptions {
+            days: DaySelection::Single(5), record: true, ..RunOptions::default() }));
+        assert_eq!(parse(&["-d", "12", "-p", "steps=10", "--param", "scale = 2"]).unwrap(), Command::Run(RunOptions {
+            days: DaySelection::Single(12),
+            parameters: vec![(String::from("steps"), String::from("10")), (String::from("scale"), String::from("2"))]
+                .into_iter().collect(),
+            ..RunOptions::default() }));
+        assert_eq!(parse(&["-b", "10", "--format", "csv"]).unwrap(), Command::Benchmark(RunOptions::default(),
+            BenchmarkOptions { runs: 10, format: ReportFormat::Csv }));
+        assert_eq!(parse(&["-c", "-j", "4", "-s", "1"]).unwrap(), Command::Check(RunOptions {
+            stars: StarSelection::First, ..RunOptions::default() }, CheckOptions { jobs: Some(4) }));
+        assert_eq!(parse(&["-e", "-d", "1"]).unwrap(), Command::Examples(RunOptions {
+            days: DaySelection::Single(1), ..RunOptions::default() }));
+        assert_eq!(parse(&["--disassemble", "program.input"]).unwrap(), Command::Intcode(IntcodeOptions {
+            tool: IntcodeTool::Disassemble, path: PathBuf::from("program.input"), inputs: vec![] }));
+        assert_eq!(parse(&["--program-input", "1, -2", "--profile", "program.input"]).unwrap(), Command::Intcode(IntcodeOptions {
+            tool: IntcodeTool::Profile, path: PathBuf::from("program.input"), inputs: vec![1, -2] }));
+        assert_eq!(parse(&["--arcade", "day_13.input"]).unwrap(), Command::Intcode(IntcodeOptions {
+            tool: IntcodeTool::Arcade, path: PathBuf::from("day_13.input"), inputs: vec![] }));
+
+        assert!(parse(&["-d", "0"]).is_err());
+        assert!(parse(&["-d", "7-3"]).is_err());
+        assert!(parse(&["-d", "1-26"]).is_err());
+        assert!(parse(&["-s", "3"]).is_err());
+        assert!(parse(&["-i", "input.txt"]).is_err());
+        assert!(parse(&["--day"]).is_err());
+        assert!(parse(&["-b", "0"]).is_err());
+        assert!(parse(&["-f", "json"]).is_err());
+        assert!(parse(&["-c", "-b", "3"]).is_err());
+        assert!(parse(&["-c", "-j", "0"]).is_err());
+        assert!(parse(&["-j", "2"]).is_err());
+        assert!(parse(&["-d", "5", "-i", "-", "-r"]).is_err());
+        assert!(parse(&["-c", "-r"]).is_err());
+        assert!(parse(&["-e", "-c"]).is_err());
+        assert!(parse(&["-p", "steps=10"]).is_err());
+        assert!(parse(&["-d", "12", "-p", "steps"]).is_err());
+        assert!(parse(&["-d", "12", "-p", "steps=10", "-e"]).is_err());
+        assert!(parse(&["--trace", "program.input", "--program-input", "1,x"]).is_err());
+        assert!(parse(&["--disassemble", "program.input", "-d", "3"]).is_err());
+        assert!(parse(&["-t", "--debug", "program.input"]).is_err());
+        assert!(parse(&["--debug", "program.input", "--trace", "program.input"]).is_err());
+        assert!(parse(&["--program-input", "1,2"]).is_err());
+        assert!(parse(&["-d", "9", "--program-input", "1"]).is_err());
+    }
+}
@@ -1,5 +1,66 @@
-use super::aoc_error::AocError;
+use std::time::Duration;
 
-pub trait AdventOfCodeCalendar {
-    fn run_day(&self, day: u32, input: Option<&str>) -> (Result<String, AocError>, Result<String, AocError>);
+use super::aoc_error::{AocError, AocResult};
+use super::manifest::Example;
+use super::solution::ParameterValues;
+
+/// Result of a single star together with the time it took to compute.
+#[derive(Debug)]
+pub struct StarRun {
+    pub result: AocResult,
+    pub time: Duration,
+}
+
+/// Results of both stars of a day and the time spent parsing the input, which is shared by both stars.
+#[derive(Debug)]
+pub struct DayRun {
+    pub parse_time: Duration,
+    pub first_star: StarRun,
+    pub second_star: StarRun,
+}
+
+impl DayRun {
+    /// Run which failed before the stars could start, e.g. on the input parsing.
+    pub fn failed(error: AocError, parse_time: Duration) -> DayRun {
+        DayRun {
+            parse_time,
+            first_star: StarRun { result: Err(error.clone()), time: Duration::ZERO },
+            second_star: StarRun { result: Err(error), time: Duration::ZERO },
+        }
+    }
+}
+
+pub trait AdventOfCodeCalendar: Send + Sync {
+    fn year(&self) -> u32;
+
+    fn run_day(&self, day: u32, input: Option<&str>) -> (Result<String, AocError>, Result<String, AocError>) {
+        let run = self.run_day_timed(day, input);
+        (run.first_star.result, run.second_star.result)
+    }
+
+    /// Same as `run_day`, measuring the parsing and both stars.
+    fn run_day_timed(&self, day: u32, input: Option<&str>) -> DayRun {
+        self.run_day_with(day, input, &ParameterValues::new())
+    }
+
+    /// Same as `run_day_timed`, the `parameters` override the puzzle defaults of the solution.
+    fn run_day_with(&self, day: u32, input: Option<&str>, parameters: &ParameterValues) -> DayRun;
+
+    /// Days for which the calendar has a solution, in ascending order.
+    fn implemented_days(&self) -> Vec<u32>;
+
+    /// Known answers of both stars of the day for its default input.
+    fn expected_answers(&self, _day: u32) -> (Option<String>, Option<String>) {
+        (None, None)
+    }
+
+    /// Examples of the day from its manifest, together with their inputs.
+    fn examples(&self, _day: u32) -> Result<Vec<(Example, String)>, AocError> {
+        Ok(vec![])
+    }
+
+    /// Stores the confirmed `answer` of the `star` as the expected one for the default input of the `day`.
+    fn record_answer(&self, _day: u32, _star: u32, _answer: &str) -> Result<(), AocError> {
+        Err(AocError::InvalidArgument(format!("Calendar {} can't record answers", self.year())))
+    }
 }
@@ -1,6 +1,7 @@
-#![feature(str_split_once)]
-
 pub mod aoc_error;
 pub mod interface;
+pub mod manifest;
+pub mod registry;
+pub mod solution;
 pub mod utils;
 pub mod year2019;
\ No newline at end of file
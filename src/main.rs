@@ -1,17 +1,285 @@
-#![feature(str_split_once)]
+mod benchmark;
+mod check;
+mod cli;
 
-mod aoc_error;
-mod interface;
-mod utils;
-mod year2019;
+use std::{env, fs, io, process, thread};
+use std::io::Read;
 
-use interface::AdventOfCodeCalendar;
+use aoc_framework::aoc_error::AocError;
+use aoc_framework::interface::AdventOfCodeCalendar;
+use aoc_framework::registry::CalendarRegistry;
+use aoc_framework::utils::file_handling::{self, Verification};
+use aoc_framework::year2019::day_13;
+use aoc_framework::year2019::intcode_computer::{IntcodeComputerError, IntcodeHardware, RunState, disassembler, utils};
+use aoc_framework::year2019::intcode_computer::debugger::Debugger;
+use aoc_framework::year2019::intcode_computer::tracer::{Profiler, TraceWriter, Tracer};
+
+use cli::{BenchmarkOptions, CheckOptions, Command, InputSource, IntcodeOptions, IntcodeTool, RunOptions};
 
 fn main() {
-    let day = 14;
-    let calendar = year2019::calendar::Calendar2019::new();
-    let (first_star, second_star) = calendar.run_day(day, None);
-    let first_star = first_star.unwrap_or_else(|error| error.to_string());
-    let second_star = second_star.unwrap_or_else(|error| error.to_string());
-    println!("Day {}: first star result: {}, second star result: {}", day, first_star, second_star);
-}
\ No newline at end of file
+    let result = match cli::parse_arguments(env::args().skip(1)) {
+        Ok(Command::Run(options)) => run(&options),
+        Ok(Command::Intcode(options)) => run_intcode_tool(&options).map(|()| true),
+        Ok(Command::Benchmark(options, benchmark_options)) => run_benchmark(&options, &benchmark_options),
+        Ok(Command::Check(options, check_options)) => run_check(&options, &check_options),
+        Ok(Command::Examples(options)) => run_examples(&options),
+        Ok(Command::Help) => {
+            println!("{}", cli::usage());
+            Ok(true)
+        },
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, cli::usage());
+            process::exit(2);
+        },
+    };
+
+    // Failed days and wrong answers exit with 1, errors which stopped the command with 2.
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(2);
+        },
+    }
+}
+
+/// Runs the selected days and stars. Returns whether all of them finished without an error.
+fn run(options: &RunOptions) -> Result<bool, AocError> {
+    let registry = CalendarRegistry::with_all_years();
+    let calendar = select_calendar(&registry, options.year)?;
+    let input = read_input(&options.input)?;
+
+    let mut all_succeeded = true;
+    for day in cli::selected_days(&options.days, &calendar.implemented_days()) {
+        let run = calendar.run_day_with(day, input.as_deref(), &options.parameters);
+        if options.show_times {
+            println!("Day {} parse: {:?}", day, run.parse_time);
+        }
+        // Answers are only known for the default input and parameters.
+        let is_default_run = input.is_none() && options.parameters.is_empty();
+        let (first_expected, second_expected) = match is_default_run {
+            true => calendar.expected_answers(day),
+            false => (None, None),
+        };
+        let stars = [
+            (options.stars.includes_first(), run.first_star, first_expected),
+            (options.stars.includes_second(), run.second_star, second_expected),
+        ];
+        for (star_index, (is_selected, star, expected)) in stars.iter().enumerate() {
+            if !is_selected {
+                continue;
+            }
+            let star_number = star_index as u32 + 1;
+            let time = match options.show_times {
+                true => format!(" ({:?})", star.time),
+                false => String::new(),
+            };
+            match &star.result {
+                Ok(answer) => {
+                    let verification = file_handling::verify_answer(answer, expected.as_deref());
+                    let status = match (is_default_run, &verification) {
+                        (false, _) => String::new(),
+                        (true, Verification::Correct) => String::from(" [correct]"),
+                        (true, Verification::Incorrect { expected }) => format!(" [incorrect, expected {}]", expected),
+                        (true, Verification::Unknown) => String::from(" [unknown]"),
+                    };
+                    println!("Day {} star {}{}{}: {}", day, star_number, time, status, answer);
+
+                    match verification {
+                        Verification::Incorrect { .. } => all_succeeded = false,
+                        Verification::Unknown if options.record => match calendar.record_answer(day, star_number, answer) {
+                            Ok(()) => println!("Day {} star {}: answer recorded", day, star_number),
+                            Err(error) => {
+                                all_succeeded = false;
+                                println!("Day {} star {}: answer not recorded: {}", day, star_number, error);
+                            },
+                        },
+                        _ => (),
+                    }
+                },
+                Err(AocError::NotImplemented { .. }) => println!("Day {} star {}: not implemented", day, star_number),
+                Err(error) => {
+                    all_succeeded = false;
+                    println!("Day {} star {}{}: error: {}", day, star_number, time, error);
+                }
+            }
+        }
+    }
+    Ok(all_succeeded)
+}
+
+fn run_benchmark(options: &RunOptions, benchmark_options: &BenchmarkOptions) -> Result<bool, AocError> {
+    let registry = CalendarRegistry::with_all_years();
+    let calendar = select_calendar(&registry, options.year)?;
+    let input = read_input(&options.input)?;
+
+    let mut all_succeeded = true;
+    let mut times = vec![];
+    for day in cli::selected_days(&options.days, &calendar.implemented_days()) {
+        match benchmark::benchmark_day(calendar, day, input.as_deref(), &options.parameters, benchmark_options.runs,
+                                       options.stars) {
+            Ok(day_times) if day_times.is_empty() => eprintln!("Day {}: not implemented", day),
+            Ok(day_times) => times.extend(day_times),
+            Err(error) => {
+                all_succeeded = false;
+                eprintln!("Day {}: error: {}", day, error);
+            },
+        }
+    }
+    print!("{}", benchmark::format_report(&times, benchmark_options.format));
+    Ok(all_succeeded)
+}
+
+/// Runs the selected days in parallel and prints their summary. Returns whether all of them finished without an
+/// error or a wrong answer.
+fn run_check(options: &RunOptions, check_options: &CheckOptions) -> Result<bool, AocError> {
+    let registry = CalendarRegistry::with_all_years();
+    let calendar = select_calendar(&registry, options.year)?;
+    let input = read_input(&options.input)?;
+    let jobs = check_options.jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |jobs| jobs.get()));
+
+    let days = cli::selected_days(&options.days, &calendar.implemented_days());
+    let summaries = check::check_days(calendar, &days, input.as_deref(), &options.parameters, options.stars, jobs);
+    print!("{}", check::format_summary(&summaries));
+    Ok(!summaries.iter().any(check::DaySummary::is_failure))
+}
+
+/// Runs the examples of the selected days and compares their answers with the expected ones. Returns whether all
+/// of them are correct.
+fn run_examples(options: &RunOptions) -> Result<bool, AocError> {
+    let registry = CalendarRegistry::with_all_years();
+    let calendar = select_calendar(&registry, options.year)?;
+
+    let mut all_succeeded = true;
+    for day in cli::selected_days(&options.days, &calendar.implemented_days()) {
+        for (example, input) in calendar.examples(day)? {
+            let run = calendar.run_day_with(day, Some(&input), &example.parameters);
+            let (first_star, second_star) = (run.first_star.result, run.second_star.result);
+            let stars = [
+                (options.stars.includes_first(), first_star, example.first_star),
+                (options.stars.includes_second(), second_star, example.second_star),
+            ];
+            for (star_index, (is_selected, result, expected)) in stars.iter().enumerate() {
+                if !is_selected || expected.is_none() {
+                    continue;
+                }
+                let star_number = star_index + 1;
+                match result {
+                    Ok(answer) => match file_handling::verify_answer(answer, expected.as_deref()) {
+                        Verification::Incorrect { expected } => {
+                            all_succeeded = false;
+                            println!("Day {} example '{}' star {} [incorrect, expected {}]: {}", day, example.name,
+                                     star_number, expected, answer);
+                        },
+                        _ => println!("Day {} example '{}' star {} [correct]: {}", day, example.name, star_number, answer),
+                    },
+                    Err(error) => {
+                        all_succeeded = false;
+                        println!("Day {} example '{}' star {}: error: {}", day, example.name, star_number, error);
+                    },
+                }
+            }
+        }
+    }
+    Ok(all_succeeded)
+}
+
+/// Returns the calendar of the `year`, or of the latest year if none is given.
+fn select_calendar(registry: &CalendarRegistry, year: Option<u32>) -> Result<&dyn AdventOfCodeCalendar, AocError> {
+    let year = year.or_else(|| registry.latest_year())
+        .ok_or_else(|| AocError::InvalidArgument(String::from("No calendar is registered")))?;
+    registry.get(year).ok_or_else(|| AocError::InvalidArgument(
+        format!("Year {} not implemented, available years: {:?}", year, registry.years())))
+}
+
+/// Returns `None` for the default input, which the calendar reads from the input files of the days.
+fn read_input(source: &InputSource) -> Result<Option<String>, AocError> {
+    Ok(match source {
+        InputSource::Default => None,
+        InputSource::File(path) => Some(fs::read_to_string(path)?),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Some(input)
+        },
+    })
+}
+
+fn run_intcode_tool(options: &IntcodeOptions) -> Result<(), AocError> {
+    const NUM_HOT_ADDRESSES: usize = 20;
+
+    let program = utils::parse_intcode_program(&fs::read_to_string(&options.path)?)?;
+    match options.tool {
+        IntcodeTool::Disassemble => print!("{}", disassembler::disassemble(&program)),
+        IntcodeTool::Debug => {
+            let mut debugger = Debugger::new(program);
+            options.inputs.iter().for_each(|input| debugger.push_input(*input));
+            debugger.run_repl(io::stdin().lock(), io::stdout())?;
+        },
+        IntcodeTool::Trace => {
+            let outputs = run_traced(program, &options.inputs, &mut TraceWriter::new(io::stdout()))?;
+            println!("Outputs: {:?}", outputs);
+        },
+        IntcodeTool::Profile => {
+            let mut profiler = Profiler::default();
+            let outputs = run_traced(program, &options.inputs, &mut profiler)?;
+            println!("Outputs: {:?}\n", outputs);
+            print!("{}", profiler.report(NUM_HOT_ADDRESSES));
+        },
+        IntcodeTool::Arcade => {
+            let score = {
+                let _raw_mode = RawTerminal::enable();
+                day_13::play(program, io::stdin().lock(), io::stdout())?
+            };
+            println!("Score: {}", score);
+        },
+    }
+    Ok(())
+}
+
+/// Switches the terminal to the raw mode with `stty` for as long as it lives, so that key presses are read
+/// immediately and not echoed. Without `stty` the terminal stays as it is and keys need to be confirmed with Enter.
+struct RawTerminal {
+    /// Settings to restore, as printed by `stty -g`.
+    saved_settings: Option<String>,
+}
+
+impl RawTerminal {
+    fn enable() -> RawTerminal {
+        let saved_settings = process::Command::new("stty").arg("-g").stderr(process::Stdio::null()).output().ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+        if saved_settings.is_some() {
+            stty(&["raw", "-echo"]);
+        }
+        RawTerminal { saved_settings }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        if let Some(settings) = &self.saved_settings {
+            stty(&[settings.as_str()]);
+        }
+    }
+}
+
+fn stty(arguments: &[&str]) {
+    // The game stays playable without the raw mode, so failures are ignored.
+    let _ = process::Command::new("stty").args(arguments).stderr(process::Stdio::null()).status();
+}
+
+fn run_traced(program: Vec<i64>, inputs: &[i64], tracer: &mut dyn Tracer) -> Result<Vec<i64>, AocError> {
+    let mut hardware = IntcodeHardware::new(program);
+    inputs.iter().for_each(|input| hardware.push_input(*input));
+
+    let mut outputs = vec![];
+    loop {
+        match hardware.run_with_tracer(tracer)? {
+            RunState::NeedsInput => return Err(IntcodeComputerError::OutOfInputs.into()),
+            RunState::Output(value) => outputs.push(value),
+            RunState::Halted => return Ok(outputs),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Example manifests list the examples of a day, the file `day_XX.examples` in the inputs folder. Every example
+//! starts with its name in brackets, followed by `key = value` lines:
+//!
+//! ```text
+//! # Comment
+//! [larger example]
+//! input = day_12_test.input
+//! part_one = 179
+//! part_two = 2772
+//! steps = 10
+//! ```
+//!
+//! `input` is the input file relative to the inputs folder, `part_one` and `part_two` are the expected answers
+//! (both optional) and all the other keys are parameters of the solver.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::aoc_error::AocError;
+use crate::utils::file_handling;
+
+const EXTENSION: &str = "examples";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub name: String,
+    /// Input file, relative to the inputs folder.
+    pub input_file: String,
+    pub first_star: Option<String>,
+    pub second_star: Option<String>,
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// Loads the examples of the day together with their inputs from the manifest in the `folder`. Days without a
+/// manifest have no examples.
+pub fn load_examples_for_day(day_number: u32, folder: &str) -> Result<Vec<(Example, String)>, AocError> {
+    let mut path = Path::new(folder).join(file_handling::get_input_filename(day_number, None));
+    path.set_extension(EXTENSION);
+    let examples = match fs::read_to_string(path) {
+        Ok(manifest) => parse_manifest(&manifest)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(error.into()),
+    };
+
+    examples.into_iter().map(|example| {
+        let input = fs::read_to_string(Path::new(folder).join(&example.input_file))?;
+        Ok((example, input))
+    }).collect()
+}
+
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Example>, AocError> {
+    let mut examples: Vec<(usize, Example)> = vec![];
+    for (line_index, line) in manifest.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']')
+                .ok_or_else(|| AocError::parse_at(String::from("Missing ']' after the example name"), line_number, None))?;
+            let example = Example {
+                name: name.trim().to_string(), input_file: String::new(), first_star: None, second_star: None,
+                parameters: BTreeMap::new(),
+            };
+            examples.push((line_number, example));
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| AocError::parse_at(format!("Expected 'key = value', found '{}'", line), line_number, None))?;
+        let (key, value) = (key.trim(), value.trim().to_string());
+        let example = match examples.last_mut() {
+            Some((_, example)) => example,
+            None => return Err(AocError::parse_at(String::from("Value outside of an example"), line_number, None)),
+        };
+        match key {
+            "input" => example.input_file = value,
+            "part_one" => example.first_star = Some(value),
+            "part_two" => example.second_star = Some(value),
+            parameter => {
+                if example.parameters.insert(parameter.to_string(), value).is_some() {
+                    return Err(AocError::parse_at(format!("Duplicate parameter '{}'", parameter), line_number, None));
+                }
+            },
+        }
+    }
+
+    examples.into_iter().map(|(line_number, example)| match example.input_file.is_empty() {
+        true => Err(AocError::parse_at(format!("Example '{}' has no input", example.name), line_number, None)),
+        false => Ok(example),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let examples = parse_manifest("\
+# Examples from the puzzle
+[first]
+input = day_12_test.input
+part_one = 179
+steps = 10
+
+[second]
+input = day_12_test_2.input
+part_two = 4686774924
+").unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "first");
+        assert_eq!(examples[0].first_star.as_deref(), Some("179"));
+        assert_eq!(examples[0].second_star, None);
+        assert_eq!(examples[0].parameters.get("steps").map(String::as_str), Some("10"));
+        assert_eq!(examples[1].input_file, "day_12_test_2.input");
+
+        assert!(matches!(parse_manifest("part_one = 1"), Err(AocError::Parse { line: Some(1), .. })));
+        assert!(matches!(parse_manifest("[first]\npart_one = 1"), Err(AocError::Parse { line: Some(1), .. })));
+        assert!(matches!(parse_manifest("[first]\ninput = a\nsteps"), Err(AocError::Parse { line: Some(3), .. })));
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+
+use crate::interface::AdventOfCodeCalendar;
+use crate::year2019;
+
+/// Maps years to their calendars. Every year module provides a `register` function adding its calendar.
+#[derive(Default)]
+pub struct CalendarRegistry {
+    calendars: BTreeMap<u32, Box<dyn AdventOfCodeCalendar>>,
+}
+
+impl CalendarRegistry {
+    pub fn new() -> CalendarRegistry {
+        CalendarRegistry { calendars: BTreeMap::new() }
+    }
+
+    /// Registry with the calendars of all the years in the crate.
+    pub fn with_all_years() -> CalendarRegistry {
+        let mut registry = CalendarRegistry::new();
+        year2019::register(&mut registry);
+        registry
+    }
+
+    /// Adds the `calendar` under its year, replacing any calendar previously registered for that year.
+    pub fn register(&mut self, calendar: Box<dyn AdventOfCodeCalendar>) {
+        self.calendars.insert(calendar.year(), calendar);
+    }
+
+    pub fn get(&self, year: u32) -> Option<&dyn AdventOfCodeCalendar> {
+        self.calendars.get(&year).map(Box::as_ref)
+    }
+
+    /// Registered years in ascending order.
+    pub fn years(&self) -> Vec<u32> {
+        self.calendars.keys().copied().collect()
+    }
+
+    pub fn latest_year(&self) -> Option<u32> {
+        self.calendars.keys().next_back().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = CalendarRegistry::with_all_years();
+        assert_eq!(registry.years(), vec![2019]);
+        assert_eq!(registry.latest_year(), Some(2019));
+        assert_eq!(registry.get(2019).unwrap().year(), 2019);
+        assert!(registry.get(2015).is_none());
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::aoc_error::{AocError, AocResult};
+use crate::interface::{DayRun, StarRun};
+
+/// Kind of the answer a part produces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnswerType {
+    Number,
+    /// Free-form text, such as letters rendered as an image.
+    Text,
+}
+
+/// Parameters given by name, e.g. from the command line or an example manifest.
+pub type ParameterValues = BTreeMap<String, String>;
+
+/// Typed parameters of a solution, such as the number of simulated steps. The defaults are the values of the puzzle,
+/// the examples from the puzzle text usually need different ones.
+pub trait Parameters: Default {
+    /// Overrides the parameter `name` with the `value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError>;
+
+    fn from_values(values: &ParameterValues) -> Result<Self, AocError> {
+        let mut parameters = Self::default();
+        for (name, value) in values {
+            parameters.set(name, value)?;
+        }
+        Ok(parameters)
+    }
+}
+
+/// Solutions without parameters.
+impl Parameters for () {
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), AocError> {
+        Err(unknown_parameter(name))
+    }
+}
+
+pub fn unknown_parameter(name: &str) -> AocError {
+    AocError::InvalidArgument(format!("Unknown parameter '{}'", name))
+}
+
+pub fn parse_parameter<T: FromStr>(name: &str, value: &str) -> Result<T, AocError> where T::Err: Display {
+    value.trim().parse().map_err(|err| AocError::InvalidArgument(format!("Invalid value '{}' of parameter '{}': {}",
+                                                                          value, name, err)))
+}
+
+/// Solution of a single day. The input is parsed once by `parse` and both parts work on the result.
+pub trait Solution {
+    type Input;
+    type Parameters: Parameters;
+
+    fn day(&self) -> u32;
+
+    /// Title of the puzzle.
+    fn title(&self) -> &'static str;
+
+    /// Answer types of the first and the second part.
+    fn answer_types(&self) -> (AnswerType, AnswerType) {
+        (AnswerType::Number, AnswerType::Number)
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+
+    fn part_one(&self, input: &Self::Input, parameters: &Self::Parameters) -> AocResult;
+
+    fn part_two(&self, _input: &Self::Input, _parameters: &Self::Parameters) -> AocResult {
+        Err(AocError::NotImplemented { day: self.day(), star: Some(2) })
+    }
+}
+
+/// Object safe form of `Solution`, so that calendars can keep solutions with different input types together.
+/// Implemented for every `Solution`.
+pub trait DaySolution: Send + Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn answer_types(&self) -> (AnswerType, AnswerType);
+
+    /// Parses the input and runs both parts on it.
+    fn run(&self, input: &str) -> (AocResult, AocResult) {
+        let run = self.run_timed(input);
+        (run.first_star.result, run.second_star.result)
+    }
+
+    /// Same as `run`, measuring the parsing and both parts separately.
+    fn run_timed(&self, input: &str) -> DayRun {
+        self.run_timed_with(input, &ParameterValues::new())
+    }
+
+    /// Same as `run_timed`, the `parameters` override the puzzle defaults.
+    fn run_timed_with(&self, input: &str, parameters: &ParameterValues) -> DayRun;
+}
+
+impl<S: Solution + Send + Sync> DaySolution for S {
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn answer_types(&self) -> (AnswerType, AnswerType) {
+        Solution::answer_types(self)
+    }
+
+    fn run_timed_with(&self, input: &str, parameters: &ParameterValues) -> DayRun {
+        let parameters = match S::Parameters::from_values(parameters) {
+            Ok(parameters) => parameters,
+            Err(error) => return DayRun::failed(error, Duration::ZERO),
+        };
+
+        let start = Instant::now();
+        let parsed = self.parse(input);
+        let parse_time = start.elapsed();
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => return DayRun::failed(error, parse_time),
+        };
+
+        let time_part = |part: &dyn Fn(&S::Input) -> AocResult| {
+            let start = Instant::now();
+            let result = part(&parsed);
+            StarRun { result, time: start.elapsed() }
+        };
+        let first_star = time_part(&|input| self.part_one(input, &parameters));
+        let second_star = time_part(&|input| self.part_two(input, &parameters));
+        DayRun { parse_time, first_star, second_star }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    #[derive(Default)]
+    struct SumParameters {
+        multiplier: Option<u32>,
+    }
+
+    impl Parameters for SumParameters {
+        fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+            match name {
+                "multiplier" => self.multiplier = Some(parse_parameter(name, value)?),
+                _ => return Err(unknown_parameter(name)),
+            }
+            Ok(())
+        }
+    }
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Parameters = SumParameters;
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Sum"
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<u32>, AocError> {
+            input.split(',').map(|value| value.parse().map_err(|_| AocError::parse(format!("Invalid value '{}'", value))))
+                .collect()
+        }
+
+        fn part_one(&self, values: &Vec<u32>, parameters: &SumParameters) -> AocResult {
+            Ok((values.iter().sum::<u32>() * parameters.multiplier.unwrap_or(1)).to_string())
+        }
+    }
+
+    #[test]
+    fn test_run_solution() {
+        let solution: Box<dyn DaySolution> = Box::new(Sum);
+        let (part_one, part_two) = solution.run("1,2,3");
+        assert_eq!(part_one.unwrap(), "6");
+        assert!(matches!(part_two, Err(AocError::NotImplemented { day: 1, star: Some(2) })));
+
+        let (part_one, part_two) = solution.run("1,x");
+        assert!(matches!(part_one, Err(AocError::Parse { .. })));
+        assert!(matches!(part_two, Err(AocError::Parse { .. })));
+
+        let parameters = vec![(String::from("multiplier"), String::from("2"))].into_iter().collect();
+        assert_eq!(solution.run_timed_with("1,2,3", &parameters).first_star.result.unwrap(), "12");
+        let parameters = vec![(String::from("steps"), String::from("2"))].into_iter().collect();
+        assert!(matches!(solution.run_timed_with("1,2,3", &parameters).first_star.result, Err(AocError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,248 @@
+//! Directed graphs kept as adjacency lists, with the search algorithms the puzzles need. Nodes are identified by
+//! keys of any hashable type, such as names or ids.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Directed graph with weighted edges. Undirected graphs add the edges in both directions.
+#[derive(Debug, Clone)]
+pub struct Graph<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    /// Target and weight of the outgoing edges of every node.
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<K> Default for Graph<K> {
+    fn default() -> Self {
+        Graph { indices: HashMap::new(), keys: vec![], edges: vec![] }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Graph<K> {
+    pub fn new() -> Graph<K> {
+        Graph::default()
+    }
+
+    /// Adds the node unless it is already in the graph.
+    pub fn add_node(&mut self, key: K) {
+        self.index_or_insert(key);
+    }
+
+    /// Adds an edge of weight 1, together with the nodes which are not in the graph yet.
+    pub fn add_edge(&mut self, from: K, to: K) {
+        self.add_weighted_edge(from, to, 1);
+    }
+
+    pub fn add_weighted_edge(&mut self, from: K, to: K, weight: u64) {
+        let from = self.index_or_insert(from);
+        let to = self.index_or_insert(to);
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, first: K, second: K) {
+        self.add_edge(first.clone(), second.clone());
+        self.add_edge(second, first);
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Nodes in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = &K> {
+        self.keys.iter()
+    }
+
+    /// Targets of the edges going out of the node.
+    pub fn neighbours<'a>(&'a self, key: &K) -> impl Iterator<Item = &'a K> {
+        let edges = self.indices.get(key).map_or(&[][..], |index| &self.edges[*index][..]);
+        edges.iter().map(move |(target, _)| &self.keys[*target])
+    }
+
+    /// Number of edges on the shortest path from the `start` to every node reachable from it.
+    pub fn bfs_distances(&self, start: &K) -> HashMap<K, usize> {
+        let (distances, _) = self.bfs(start);
+        distances.into_iter().enumerate()
+            .filter_map(|(index, distance)| distance.map(|distance| (self.keys[index].clone(), distance)))
+            .collect()
+    }
+
+    /// Path with the fewest edges from the `start` to the `goal`, both included.
+    pub fn shortest_path(&self, start: &K, goal: &K) -> Option<Vec<K>> {
+        let goal = *self.indices.get(goal)?;
+        let (distances, predecessors) = self.bfs(start);
+        distances[goal]?;
+
+        let mut path = vec![self.keys[goal].clone()];
+        let mut current = goal;
+        while let Some(predecessor) = predecessors[current] {
+            path.push(self.keys[predecessor].clone());
+            current = predecessor;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Nodes reachable from the `start` in depth-first pre-order, neighbours are visited in the order of their edges.
+    pub fn dfs(&self, start: &K) -> Vec<K> {
+        let mut visited = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = self.indices.get(start).copied().into_iter().collect::<Vec<usize>>();
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(self.keys[node].clone());
+            stack.extend(self.edges[node].iter().rev().map(|(target, _)| *target).filter(|target| !visited[*target]));
+        }
+        order
+    }
+
+    /// Length of the shortest weighted path from the `start` to every node reachable from it.
+    pub fn dijkstra(&self, start: &K) -> HashMap<K, u64> {
+        let mut distances: Vec<Option<u64>> = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        if let Some(start) = self.indices.get(start) {
+            distances[*start] = Some(0);
+            queue.push(Reverse((0, *start)));
+        }
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distances[node].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for (target, weight) in &self.edges[node] {
+                let target_distance = distance + weight;
+                if distances[*target].is_none_or(|best| target_distance < best) {
+                    distances[*target] = Some(target_distance);
+                    queue.push(Reverse((target_distance, *target)));
+                }
+            }
+        }
+
+        distances.into_iter().enumerate()
+            .filter_map(|(index, distance)| distance.map(|distance| (self.keys[index].clone(), distance)))
+            .collect()
+    }
+
+    /// Lowest common ancestor of the nodes `a` and `b` in the tree with the edges going from parents to children.
+    pub fn lowest_common_ancestor(&self, root: &K, a: &K, b: &K) -> Option<K> {
+        let path_to_a = self.shortest_path(root, a)?;
+        let path_to_b = self.shortest_path(root, b)?;
+        path_to_a.into_iter().zip(path_to_b).take_while(|(a, b)| a == b).last().map(|(ancestor, _)| ancestor)
+    }
+
+    /// Orders the nodes so that every edge goes from an earlier node to a later one. Nodes which don't depend on each
+    /// other keep the order they were added in. `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<K>> {
+        let mut in_degrees = vec![0; self.len()];
+        for edges in &self.edges {
+            for (target, _) in edges {
+                in_degrees[*target] += 1;
+            }
+        }
+
+        let mut ready = (0..self.len()).filter(|node| in_degrees[*node] == 0).collect::<VecDeque<usize>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(self.keys[node].clone());
+            for (target, _) in &self.edges[node] {
+                in_degrees[*target] -= 1;
+                if in_degrees[*target] == 0 {
+                    ready.push_back(*target);
+                }
+            }
+        }
+
+        match order.len() == self.len() {
+            true => Some(order),
+            false => None,
+        }
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.topological_sort().is_none()
+    }
+
+    fn index_or_insert(&mut self, key: K) -> usize {
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+        let index = self.keys.len();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        self.edges.push(vec![]);
+        index
+    }
+
+    /// Distances and predecessors on the shortest paths from the `start`, indexed by the nodes.
+    fn bfs(&self, start: &K) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.len()];
+        let mut predecessors = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        if let Some(start) = self.indices.get(start) {
+            distances[*start] = Some(0);
+            queue.push_back(*start);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].unwrap();
+            for (target, _) in &self.edges[node] {
+                if distances[*target].is_none() {
+                    distances[*target] = Some(distance + 1);
+                    predecessors[*target] = Some(node);
+                    queue.push_back(*target);
+                }
+            }
+        }
+        (distances, predecessors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_searches() {
+        let mut graph = Graph::new();
+        for (from, to) in [("A", "B"), ("A", "C"), ("B", "D"), ("C", "D"), ("D", "E")].iter() {
+            graph.add_edge(*from, *to);
+        }
+        graph.add_weighted_edge("A", "E", 5);
+        graph.add_node("F");
+
+        assert_eq!(graph.bfs_distances(&"A").get("E"), Some(&1));
+        assert_eq!(graph.shortest_path(&"B", &"E"), Some(vec!["B", "D", "E"]));
+        assert_eq!(graph.shortest_path(&"E", &"A"), None);
+        assert_eq!(graph.dfs(&"A"), vec!["A", "B", "D", "E", "C"]);
+        assert_eq!(graph.dijkstra(&"A").get("E"), Some(&3));
+        assert_eq!(graph.dijkstra(&"A").get("F"), None);
+        assert_eq!(graph.lowest_common_ancestor(&"A", &"B", &"C"), Some("A"));
+        assert_eq!(graph.lowest_common_ancestor(&"A", &"B", &"D"), Some("B"));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut graph = Graph::new();
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 2);
+        assert_eq!(graph.topological_sort(), Some(vec![3, 1, 2]));
+        assert!(!graph.has_cycle());
+
+        graph.add_edge(2, 3);
+        assert!(graph.has_cycle());
+    }
+}
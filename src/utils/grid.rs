@@ -0,0 +1,327 @@
+//! Points, directions and grids on the integer plane. The coordinates are screen-like: `x` grows to the right and
+//! `y` grows down, so `Direction::Up` decreases `y` and rendering starts with the lowest `y`.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// Point from coordinates of another integer type, `None` if they don't fit into `i32`.
+    pub fn try_from_coordinates<T: Copy>(x: T, y: T) -> Option<Point> where i32: TryFrom<T> {
+        Some(Point { x: i32::try_from(x).ok()?, y: i32::try_from(y).ok()? })
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Neighbouring point in the `direction`.
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+
+    /// The four orthogonal neighbours, in the order of `Direction::ALL`.
+    pub fn neighbours(self) -> [Point; 4] {
+        let [up, right, down, left] = Direction::ALL;
+        [self.step(up), self.step(right), self.step(down), self.step(left)]
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All the directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self.index() + 2) % 4]
+    }
+
+    fn index(self) -> usize {
+        Direction::ALL.iter().position(|direction| *direction == self).unwrap()
+    }
+}
+
+/// Smallest rectangle containing a set of points, both corners are included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// `None` when there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<BoundingBox> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoundingBox { min: first, max: first }, |bounds, point| BoundingBox {
+            min: Point::new(bounds.min.x.min(point.x), bounds.min.y.min(point.y)),
+            max: Point::new(bounds.max.x.max(point.x), bounds.max.y.max(point.y)),
+        }))
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Points of the box row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    /// Draws the box row by row, every row ends with a new line.
+    pub fn render(&self, mut draw: impl FnMut(Point) -> char) -> String {
+        let mut output = String::with_capacity((self.width() + 1) * self.height());
+        for y in self.min.y..=self.max.y {
+            for x in self.min.x..=self.max.x {
+                output.push(draw(Point::new(x, y)));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Rectangular grid with its top left corner at the origin, storing every cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> DenseGrid<T> {
+        DenseGrid { width, height, cells: vec![value; width * height] }
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// `None` if the rows don't have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<DenseGrid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        Some(DenseGrid { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        match self.cells.is_empty() {
+            true => None,
+            false => Some(BoundingBox {
+                min: Point::ORIGIN, max: Point::new(self.width as i32 - 1, self.height as i32 - 1) }),
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(move |index| &mut self.cells[index])
+    }
+
+    /// Cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate()
+            .map(move |(index, cell)| (Point::new((index % width) as i32, (index / width) as i32), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn render(&self, draw: impl Fn(&T) -> char) -> String {
+        match self.bounding_box() {
+            Some(bounds) => bounds.render(|point| draw(&self.cells[self.index(point).unwrap()])),
+            None => String::new(),
+        }
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let (x, y) = (usize::try_from(point.x).ok()?, usize::try_from(point.y).ok()?);
+        match x < self.width && y < self.height {
+            true => Some(y * self.width + x),
+            false => None,
+        }
+    }
+}
+
+/// Unbounded grid storing only the cells which were set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid { cells: HashMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    /// Returns the previous value of the cell.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// Bounding box of the set cells.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.cells.keys().copied())
+    }
+
+    /// Draws the bounding box of the set cells, `draw` gets `None` for the cells which were not set.
+    pub fn render(&self, draw: impl Fn(Option<&T>) -> char) -> String {
+        match self.bounding_box() {
+            Some(bounds) => bounds.render(|point| draw(self.cells.get(&point))),
+            None => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_and_directions() {
+        let point = Point::new(2, -3);
+        assert_eq!(point.manhattan_distance(Point::ORIGIN), 5);
+        assert_eq!(point.step(Direction::Up), Point::new(2, -4));
+        assert_eq!(point.neighbours(), [Point::new(2, -4), Point::new(3, -3), Point::new(2, -2), Point::new(1, -3)]);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        assert_eq!(Point::try_from_coordinates(1i64, 1i64 << 40), None);
+    }
+
+    #[test]
+    fn test_grids() {
+        let mut sparse = SparseGrid::new();
+        sparse.insert(Point::new(-1, 2), '#');
+        sparse.insert(Point::new(1, 3), '#');
+        let bounds = sparse.bounding_box().unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (3, 2));
+        assert!(bounds.contains(Point::new(0, 2)));
+        assert_eq!(sparse.render(|cell| *cell.unwrap_or(&'.')), "#..\n..#\n");
+
+        let mut dense = DenseGrid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        *dense.get_mut(Point::new(1, 2)).unwrap() = 0;
+        assert_eq!(dense.get(Point::new(2, 0)), None);
+        assert_eq!(dense.iter().nth(3), Some((Point::new(1, 1), &4)));
+        assert_eq!(dense.render(|cell| std::char::from_digit(*cell, 10).unwrap()), "12\n34\n50\n");
+        assert!(DenseGrid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+}
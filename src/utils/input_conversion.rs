@@ -0,0 +1,238 @@
+//! Parsers of the input formats the puzzles share. Errors point to the line and column of the offending part of
+//! the input.
+
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::aoc_error::AocError;
+
+/// Line of the input which knows its position, so that errors can point into it.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// 1-based line number.
+    pub number: usize,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(index, text)| Line { text, number: index + 1 })
+}
+
+/// Part of a line, such as a single value. It is only created from its line, so it always knows its byte offset in
+/// the line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Part<'a> {
+    pub text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Part<'a> {
+    pub fn trim(self) -> Part<'a> {
+        let leading_whitespace = self.text.len() - self.text.trim_start().len();
+        Part { text: self.text.trim(), offset: self.offset + leading_whitespace }
+    }
+
+    /// The part from the byte `index` on, which must be on a character boundary.
+    pub fn slice_from(self, index: usize) -> Part<'a> {
+        Part { text: &self.text[index..], offset: self.offset + index }
+    }
+
+    pub fn split(self, separator: char) -> impl Iterator<Item = Part<'a>> {
+        self.split_by(move |character| character == separator)
+    }
+
+    /// Non-empty parts separated by any whitespace.
+    pub fn split_whitespace(self) -> impl Iterator<Item = Part<'a>> {
+        self.split_by(char::is_whitespace).filter(|part| !part.text.is_empty())
+    }
+
+    /// Splits the part on the first `separator`.
+    pub fn split_once(self, separator: &str) -> Option<(Part<'a>, Part<'a>)> {
+        let index = self.text.find(separator)?;
+        Some((Part { text: &self.text[..index], offset: self.offset }, self.slice_from(index + separator.len())))
+    }
+
+    fn split_by(self, is_separator: impl Fn(char) -> bool) -> impl Iterator<Item = Part<'a>> {
+        let Part { text, offset } = self;
+        let mut start = 0;
+        let separators = text.char_indices().filter(move |(_, character)| is_separator(*character));
+        separators.map(Some).chain(Some(None)).map(move |separator| {
+            let end = separator.map_or(text.len(), |(index, _)| index);
+            let part = Part { text: &text[start..end], offset: offset + start };
+            if let Some((index, character)) = separator {
+                start = index + character.len_utf8();
+            }
+            part
+        })
+    }
+}
+
+impl<'a> Line<'a> {
+    /// The whole text of the line.
+    pub fn whole(&self) -> Part<'a> {
+        Part { text: self.text, offset: 0 }
+    }
+
+    /// 1-based column of the `part` of the line.
+    pub fn column_of(&self, part: Part) -> usize {
+        self.text[..part.offset].chars().count() + 1
+    }
+
+    pub fn error(&self, message: String) -> AocError {
+        AocError::parse_at(message, self.number, None)
+    }
+
+    /// Error pointing to the `part` of the line.
+    pub fn error_at(&self, part: Part, message: String) -> AocError {
+        AocError::parse_at(message, self.number, Some(self.column_of(part)))
+    }
+
+    /// Parses the `part` of the line, ignoring whitespace around it.
+    pub fn parse<T: FromStr>(&self, part: Part<'a>) -> Result<T, AocError> where T::Err: Display {
+        let part = part.trim();
+        part.text.parse().map_err(|err| self.error_at(part, format!("Invalid value '{}': {}", part.text, err)))
+    }
+
+    /// Parses the values in the `part` separated by the `separator`.
+    pub fn separated<T: FromStr>(&self, part: Part<'a>, separator: char) -> Result<Vec<T>, AocError>
+    where T::Err: Display {
+        part.split(separator).map(|value| self.parse(value)).collect()
+    }
+
+    /// Splits the `part` on the first `separator`, which must be present.
+    pub fn split_once(&self, part: Part<'a>, separator: &str) -> Result<(Part<'a>, Part<'a>), AocError> {
+        part.split_once(separator).ok_or_else(|| self.error_at(part, format!("Expected '{}'", separator)))
+    }
+
+    /// Parses a comma separated list of `AMOUNT NAME` items, such as `7 A, 1 B`.
+    pub fn quantities<T: FromStr>(&self, part: Part<'a>) -> Result<Vec<(T, &'a str)>, AocError> where T::Err: Display {
+        part.split(',').map(|item| {
+            let item = item.trim();
+            match item.split_once(" ") {
+                Some((amount, name)) if !name.text.trim().is_empty() => Ok((self.parse(amount)?, name.text.trim())),
+                _ => Err(self.error_at(item, format!("Expected 'AMOUNT NAME', found '{}'", item.text))),
+            }
+        }).collect()
+    }
+
+    /// Parses the whole line as a vector with the named components, such as `<x=1, y=-2, z=3>`.
+    pub fn named_vector<T: FromStr, const N: usize>(&self, names: [&str; N]) -> Result<[T; N], AocError>
+    where T::Err: Display {
+        let text = self.whole().trim();
+        let components = match text.text.starts_with('<') && text.text.ends_with('>') && text.text.len() > 1 {
+            true => Part { text: &text.text[1..text.text.len() - 1], offset: text.offset + 1 },
+            false => return Err(self.error_at(text, String::from("Expected a vector in '<' and '>'"))),
+        };
+
+        let components = components.split(',').collect::<Vec<Part>>();
+        if components.len() != N {
+            return Err(self.error(format!("Expected {} components, found {}", N, components.len())));
+        }
+        let values = components.iter().zip(names.iter()).map(|(component, name)| {
+            let (component_name, value) = self.split_once(*component, "=")?;
+            match component_name.text.trim() == *name {
+                true => self.parse(value),
+                false => Err(self.error_at(component_name.trim(), format!("Expected component '{}'", name))),
+            }
+        }).collect::<Result<Vec<T>, AocError>>()?;
+        // The number of the values has been checked above.
+        Ok(<[T; N]>::try_from(values).ok().unwrap())
+    }
+}
+
+/// Parses the first line of the input as comma separated values.
+pub fn comma_separated<T: FromStr>(input: &str) -> Result<Vec<T>, AocError> where T::Err: Display {
+    let line = lines(input).next().ok_or_else(|| AocError::parse(String::from("Empty input")))?;
+    line.separated(line.whole(), ',')
+}
+
+/// Parses values separated by any whitespace, including new lines.
+pub fn whitespace_separated<T: FromStr>(input: &str) -> Result<Vec<T>, AocError> where T::Err: Display {
+    let mut values = vec![];
+    for line in lines(input) {
+        for value in line.whole().split_whitespace() {
+            values.push(line.parse(value)?);
+        }
+    }
+    Ok(values)
+}
+
+/// Parses the input as rows of characters, all the rows must have the same length.
+pub fn char_grid(input: &str) -> Result<Vec<Vec<char>>, AocError> {
+    let mut grid: Vec<Vec<char>> = vec![];
+    for line in lines(input) {
+        let row = line.text.chars().collect::<Vec<char>>();
+        if let Some(first_row) = grid.first() {
+            if row.len() != first_row.len() {
+                return Err(line.error(format!("Expected a row of {} characters, found {}", first_row.len(), row.len())));
+            }
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+/// Parses lines with two non-empty parts split by the `separator`, such as `A)B` or `key: value`. Empty lines are
+/// skipped.
+pub fn pairs<'a>(input: &'a str, separator: &str) -> Result<Vec<(&'a str, &'a str)>, AocError> {
+    lines(input).filter(|line| !line.text.trim().is_empty()).map(|line| {
+        let (first, second) = line.split_once(line.whole(), separator)?;
+        for part in [first, second].iter() {
+            if part.text.trim().is_empty() {
+                return Err(line.error_at(*part, format!("Expected a value around '{}'", separator)));
+            }
+        }
+        Ok((first.text.trim(), second.text.trim()))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(error: AocError) -> (Option<usize>, Option<usize>) {
+        match error {
+            AocError::Parse { line, column, .. } => (line, column),
+            _ => panic!("Not a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_separated_values() {
+        assert_eq!(comma_separated::<i64>("1, -2,3\n").unwrap(), vec![1, -2, 3]);
+        assert_eq!(position(comma_separated::<i64>("1,2,x3").unwrap_err()), (Some(1), Some(5)));
+        assert_eq!(whitespace_separated::<u32>("1 2\n  3\n").unwrap(), vec![1, 2, 3]);
+        assert_eq!(position(whitespace_separated::<u32>("1 2\n  3 -4").unwrap_err()), (Some(2), Some(5)));
+    }
+
+    #[test]
+    fn test_structured_lines() {
+        assert_eq!(pairs("COM)B\nB)C\n", ")").unwrap(), vec![("COM", "B"), ("B", "C")]);
+        assert_eq!(position(pairs("COM)B\nB)", ")").unwrap_err()), (Some(2), Some(3)));
+        assert_eq!(position(char_grid("#.#\n..\n").unwrap_err()), (Some(2), None));
+
+        let line = lines("<x=-1, y=0, z=2>").next().unwrap();
+        assert_eq!(line.named_vector::<i32, 3>(["x", "y", "z"]).unwrap(), [-1, 0, 2]);
+        assert_eq!(position(line.named_vector::<i32, 3>(["x", "z", "y"]).unwrap_err()), (Some(1), Some(8)));
+        assert!(line.named_vector::<i32, 2>(["x", "y"]).is_err());
+
+        let line = lines("7 A, 1 BC => 1 FUEL").next().unwrap();
+        let (inputs, output) = line.split_once(line.whole(), "=>").unwrap();
+        assert_eq!(line.quantities::<u64>(inputs).unwrap(), vec![(7, "A"), (1, "BC")]);
+        assert_eq!(line.quantities::<u64>(output).unwrap(), vec![(1, "FUEL")]);
+
+        let line = lines("7 A, x B => 1 FUEL").next().unwrap();
+        assert_eq!(position(line.quantities::<u64>(line.whole()).unwrap_err()), (Some(1), Some(6)));
+    }
+
+    #[test]
+    fn test_part_offsets() {
+        let line = lines("ä b,\u{a0}c ,d").next().unwrap();
+        let columns = |parts: Vec<Part<'static>>| parts.iter().map(|part| (part.text, line.column_of(*part))).collect::<Vec<_>>();
+        assert_eq!(columns(line.whole().split(',').collect()), vec![("ä b", 1), ("\u{a0}c ", 5), ("d", 9)]);
+        assert_eq!(columns(line.whole().split_whitespace().collect()), vec![("ä", 1), ("b,", 3), ("c", 6), (",d", 8)]);
+        let (first, second) = line.whole().split_once("b,").unwrap();
+        assert_eq!(columns(vec![first.trim(), second.trim(), second.slice_from(2)]), vec![("ä", 1), ("c ,d", 6), ("c ,d", 6)]);
+    }
+}
@@ -0,0 +1,175 @@
+pub mod file_handling {
+    use std::{io, fs, path::PathBuf};
+
+    /// Outcome of comparing an answer with the stored one.
+    #[derive(Debug, PartialEq)]
+    pub enum Verification {
+        Correct,
+        Incorrect { expected: String },
+        /// There is no stored answer.
+        Unknown,
+    }
+
+    pub fn get_input_filename(day_number: u32, filename_suffix: Option<&str>) -> String {
+        let suffix = filename_suffix.unwrap_or("").to_owned();
+        format!("day_{:0>2}{}", day_number, suffix)
+    }
+
+    pub fn get_input_extension() -> String {
+        String::from("input")
+    }
+
+    pub fn get_result_extension() -> String {
+        String::from("result")
+    }
+
+    pub fn get_input_for_day(day_number: u32, filename_suffix: Option<&str>, folder: &str) -> Result<String, io::Error> {
+        let path = get_whole_file_path(folder, &get_input_filename(day_number, filename_suffix), &get_input_extension());
+        fs::read_to_string(path)
+    }
+
+    /// Line which starts a multi-line answer in a result file.
+    const BLOCK_MARKER: &str = "|";
+    /// Indentation of the lines of a multi-line answer in a result file.
+    const BLOCK_INDENT: &str = "    ";
+
+    /// Reads the expected answers of the day from its result file. The first line holds the answer of the first
+    /// star and the second line the one of the second star; an empty first line means only the second is known.
+    /// A multi-line answer is stored as a `|` line followed by the lines of the answer, each indented by four spaces.
+    pub fn get_expected_answers_for_day(day_number: u32, filename_suffix: Option<&str>, folder: &str)
+        -> Result<(Option<String>, Option<String>), io::Error> {
+        let path = get_whole_file_path(folder, &get_input_filename(day_number, filename_suffix), &get_result_extension());
+        Ok(parse_results(&fs::read_to_string(path)?))
+    }
+
+    pub fn verify_answer(answer: &str, expected: Option<&str>) -> Verification {
+        match expected {
+            Some(expected) if expected == answer => Verification::Correct,
+            Some(expected) => Verification::Incorrect { expected: expected.to_string() },
+            None => Verification::Unknown,
+        }
+    }
+
+    /// Stores the `answer` of the `star` in the result file of the day, keeping the answer of the other star.
+    pub fn record_answer_for_day(day_number: u32, star: u32, answer: &str, folder: &str) -> Result<(), io::Error> {
+        if answer.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Empty answers can't be recorded"));
+        }
+
+        let path = get_whole_file_path(folder, &get_input_filename(day_number, None), &get_result_extension());
+        let (first_star_answer, second_star_answer) = match fs::read_to_string(&path) {
+            Ok(results) => parse_results(&results),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (None, None),
+            Err(error) => return Err(error),
+        };
+
+        let results = match (star, first_star_answer, second_star_answer) {
+            (1, _, None) => format!("{}\n", format_result(answer)),
+            (1, _, Some(second)) => format!("{}\n{}\n", format_result(answer), format_result(&second)),
+            (2, first, _) => format!("{}\n{}\n", format_result(&first.unwrap_or_default()), format_result(answer)),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid star {}", star))),
+        };
+        fs::write(path, results)
+    }
+
+    fn parse_results(results: &str) -> (Option<String>, Option<String>) {
+        let mut lines = results.lines().peekable();
+        let mut next_answer = || {
+            let line = lines.next()?;
+            if line != BLOCK_MARKER {
+                return Some(line.to_string());
+            }
+            let mut block = vec![];
+            while let Some(block_line) = lines.next_if(|block_line| block_line.starts_with(BLOCK_INDENT)) {
+                block.push(&block_line[BLOCK_INDENT.len()..]);
+            }
+            Some(block.join("\n"))
+        };
+        // Allow to have solutions only for the second star.
+        let first_star_answer = next_answer().filter(|answer| !answer.is_empty());
+        let second_star_answer = next_answer();
+        (first_star_answer, second_star_answer)
+    }
+
+    /// The answer as it is written to a result file, without the final new line.
+    fn format_result(answer: &str) -> String {
+        match answer.contains('\n') || answer == BLOCK_MARKER {
+            true => std::iter::once(BLOCK_MARKER.to_string())
+                .chain(answer.split('\n').map(|line| format!("{}{}", BLOCK_INDENT, line)))
+                .collect::<Vec<String>>().join("\n"),
+            false => answer.to_string(),
+        }
+    }
+
+    fn get_whole_file_path(folder: &str, file_name: &str, extension: &str) -> PathBuf {
+        let mut input_file_path = PathBuf::from(folder);
+        input_file_path.push(file_name);
+        input_file_path.set_extension(extension);
+        input_file_path
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::Path;
+
+        use super::*;
+
+        #[test]
+        fn test_record_answer() {
+            let folder = std::env::temp_dir().join(format!("aoc_record_answer_{}", std::process::id()));
+            fs::create_dir_all(&folder).unwrap();
+            let folder = folder.to_str().unwrap();
+
+            record_answer_for_day(3, 2, "66076", folder).unwrap();
+            assert_eq!(get_expected_answers_for_day(3, None, folder).unwrap(), (None, Some(String::from("66076"))));
+            record_answer_for_day(3, 1, "806", folder).unwrap();
+            assert_eq!(get_expected_answers_for_day(3, None, folder).unwrap(),
+                       (Some(String::from("806")), Some(String::from("66076"))));
+            assert!(record_answer_for_day(3, 1, "", folder).is_err());
+
+            record_answer_for_day(3, 1, "\n#.\n.#\n", folder).unwrap();
+            assert_eq!(fs::read_to_string(Path::new(folder).join("day_03.result")).unwrap(),
+                       "|\n    \n    #.\n    .#\n    \n66076\n");
+            assert_eq!(get_expected_answers_for_day(3, None, folder).unwrap(),
+                       (Some(String::from("\n#.\n.#\n")), Some(String::from("66076"))));
+            record_answer_for_day(3, 2, "|", folder).unwrap();
+            assert_eq!(get_expected_answers_for_day(3, None, folder).unwrap(),
+                       (Some(String::from("\n#.\n.#\n")), Some(String::from("|"))));
+
+            assert_eq!(verify_answer("806", Some("806")), Verification::Correct);
+            assert_eq!(verify_answer("805", Some("806")), Verification::Incorrect { expected: String::from("806") });
+            fs::remove_dir_all(folder).unwrap();
+        }
+    }
+}
+
+pub mod graph;
+pub mod grid;
+pub mod input_conversion;
+
+pub mod performance {
+    use std::collections::HashMap;
+    pub struct Cached<T, U, V>
+    where T: Fn(U) -> V,
+    {
+        calculation: T,
+        cache: HashMap<U, V>,
+    }
+
+    impl<T, U, V> Cached<T, U, V>
+    where T: Fn(U) -> V,
+          U: std::cmp::Eq, U: std::hash::Hash, U: std::clone::Clone,
+    {
+        pub fn new(calculation: T) -> Cached<T, U, V> {
+            Cached { calculation, cache: HashMap::new() }
+        }
+
+        pub fn calculate(&mut self, argument: U) -> &V {
+            if !self.cache.contains_key(&argument) {
+                self.cache.insert(argument.clone(), (self.calculation)(argument.clone()));
+            }
+            self.cache.get(&argument).unwrap()
+        }
+    }
+
+}
\ No newline at end of file
@@ -1,16 +1,28 @@
-use crate::interface::AdventOfCodeCalendar;
+use std::collections::BTreeMap;
+
+use std::time::Duration;
+
+use crate::interface::{AdventOfCodeCalendar, DayRun};
+use crate::manifest::{self, Example};
+use crate::solution::{DaySolution, ParameterValues};
 use crate::utils::file_handling;
 use crate::aoc_error::AocError;
 
 use super::configuration;
-use super::{day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10,
-            day_11, day_12, day_13, day_14};
 
-pub struct Calendar2019;
+/// Calendar running the solutions registered in `year2019::solutions`.
+pub struct Calendar2019 {
+    solutions: BTreeMap<u32, Box<dyn DaySolution>>,
+}
 
 impl Calendar2019 {
     pub fn new() -> Calendar2019 {
-        Calendar2019 {}
+        let solutions = super::solutions().into_iter().map(|solution| (solution.day(), solution)).collect();
+        Calendar2019 { solutions }
+    }
+
+    pub fn solution(&self, day: u32) -> Option<&dyn DaySolution> {
+        self.solutions.get(&day).map(Box::as_ref)
     }
 }
 
@@ -21,7 +33,16 @@ impl Default for Calendar2019 {
 }
 
 impl AdventOfCodeCalendar for Calendar2019 {
-    fn run_day(&self, day_number: u32, input: Option<&str>) -> (Result<String, AocError>, Result<String, AocError>) {
+    fn year(&self) -> u32 {
+        2019
+    }
+
+    fn run_day_with(&self, day_number: u32, input: Option<&str>, parameters: &ParameterValues) -> DayRun {
+        let solution = match self.solution(day_number) {
+            Some(solution) => solution,
+            None => return DayRun::failed(AocError::NotImplemented { day: day_number, star: None }, Duration::ZERO),
+        };
+
         let loaded_input: String;
         let input = match input {
             Some(input) => input,
@@ -32,32 +53,29 @@ impl AdventOfCodeCalendar for Calendar2019 {
                         &loaded_input
                     }
                     Err(error) => {
-                        let message = format!("IO error: {}", error.to_string());
-                        return (Err(AocError::new(message.clone())), Err(AocError::new(message)))
+                        return DayRun::failed(AocError::InputMissing { day: day_number, source: error }, Duration::ZERO)
                     }
                 }
             }
         };
 
-        match day_number {
-            1 => (day_01::first_star(input), day_01::second_star(input)),
-            2 => (day_02::first_star(input), day_02::second_star(input)),
-            3 => (day_03::first_star(input), day_03::second_star(input)),
-            4 => (day_04::first_star(input), day_04::second_star(input)),
-            5 => (day_05::first_star(input), day_05::second_star(input)),
-            6 => (day_06::first_star(input), day_06::second_star(input)),
-            7 => (day_07::first_star(input), day_07::second_star(input)),
-            8 => (day_08::first_star(input), day_08::second_star(input)),
-            9 => (day_09::first_star(input), day_09::second_star(input)),
-            10 => (day_10::first_star(input), day_10::second_star(input)),
-            11 => (day_11::first_star(input), day_11::second_star(input)),
-            12 => (day_12::first_star(input), day_12::second_star(input)),
-            13 => (day_13::first_star(input), Err(AocError::new("Not implemented".to_string()))),
-            14 => (day_14::first_star(input), Err(AocError::new("Not implemented".to_string()))),
-            _ => {
-                let message = format!("Day {} not implemented", day_number);
-                (Err(AocError::new(message.clone())), Err(AocError::new(message)))
-            },
-        }
+        solution.run_timed_with(input, parameters)
+    }
+
+    fn implemented_days(&self) -> Vec<u32> {
+        self.solutions.keys().copied().collect()
+    }
+
+    fn expected_answers(&self, day: u32) -> (Option<String>, Option<String>) {
+        file_handling::get_expected_answers_for_day(day, None, configuration::get_inputs_folder_path())
+            .unwrap_or((None, None))
+    }
+
+    fn examples(&self, day: u32) -> Result<Vec<(Example, String)>, AocError> {
+        manifest::load_examples_for_day(day, configuration::get_inputs_folder_path())
+    }
+
+    fn record_answer(&self, day: u32, star: u32, answer: &str) -> Result<(), AocError> {
+        Ok(file_handling::record_answer_for_day(day, star, answer, configuration::get_inputs_folder_path())?)
     }
 }
@@ -1,32 +1,42 @@
 use std::cmp::max;
 
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::Solution;
 use crate::utils::{input_conversion, performance::Cached};
 
-pub fn first_star(input: &str) -> AocResult {
-    // TODO: ugly, fix.
-    let input = match input_conversion::input_to_lines(input) {
-        Ok(input) => input,
-        Err(_) => return Err(AocError::new(String::from("Could not convert to lines")))
-    };
-    let mut cached_fuel_for_mass = Cached::new(fuel_for_mass);
-    let total_fuel_for_mass: i32 = input.into_iter()
-                                    .map(|mass| *cached_fuel_for_mass.calculate(mass))
-                                    .sum();
-    Ok(total_fuel_for_mass.to_string())
-}
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+    type Parameters = ();
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "The Tyranny of the Rocket Equation"
+    }
 
-pub fn second_star(input: &str) -> AocResult {
-    // TODO: ugly, fix.
-    let input = match input_conversion::input_to_lines(input) {
-        Ok(input) => input,
-        Err(_) => return Err(AocError::new(String::from("Could not convert to lines")))
-    };
-    let mut cached_fuel_for_mass_recursive = Cached::new(fuel_for_mass_recursive);
-    let total_fuel_for_mass: i32 = input.into_iter()
-                                    .map(|mass| *cached_fuel_for_mass_recursive.calculate(mass))
-                                    .sum();
-    Ok(total_fuel_for_mass.to_string())
+    fn parse(&self, input: &str) -> Result<Vec<i32>, AocError> {
+        input_conversion::whitespace_separated(input)
+    }
+
+    fn part_one(&self, masses: &Vec<i32>, _parameters: &()) -> AocResult {
+        let mut cached_fuel_for_mass = Cached::new(fuel_for_mass);
+        let total_fuel_for_mass: i32 = masses.iter()
+                                        .map(|mass| *cached_fuel_for_mass.calculate(*mass))
+                                        .sum();
+        Ok(total_fuel_for_mass.to_string())
+    }
+
+    fn part_two(&self, masses: &Vec<i32>, _parameters: &()) -> AocResult {
+        let mut cached_fuel_for_mass_recursive = Cached::new(fuel_for_mass_recursive);
+        let total_fuel_for_mass: i32 = masses.iter()
+                                        .map(|mass| *cached_fuel_for_mass_recursive.calculate(*mass))
+                                        .sum();
+        Ok(total_fuel_for_mass.to_string())
+    }
 }
 
 fn fuel_for_mass_recursive(mass: i32) -> i32 {
@@ -1,36 +1,77 @@
 use crate::aoc_error::{AocResult, AocError};
-use super::intcode_computer::{IntcodeComputer, utils};
-
-pub fn first_star(input: &str) -> AocResult {
-    // Input is a single line of numbers.
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let mut program = utils::parse_intcode_program(input)?;
-    let mut computer = IntcodeComputer::new(None);
-    program[1] = 12;
-    program[2] = 2;
-
-    computer.start(program, None, vec![])?;
-    let output = computer.wait_for_result()?;
-    Ok(output.to_string())
+use crate::solution::{self, Parameters, Solution};
+use super::intcode_computer::{IntcodeHardware, Program, utils};
+
+pub struct Day02;
+
+pub struct Day02Parameters {
+    /// Set the noun and verb of the first part to 12 and 2, which the examples from the puzzle text don't do.
+    pub restore_alarm: bool,
+    /// Output the second part looks for.
+    pub target: i64,
+}
+
+impl Default for Day02Parameters {
+    fn default() -> Self {
+        Day02Parameters { restore_alarm: true, target: 19690720 }
+    }
+}
+
+impl Parameters for Day02Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "restore_alarm" => self.restore_alarm = solution::parse_parameter(name, value)?,
+            "target" => self.target = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
+        }
+        Ok(())
+    }
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    const EXPECTED_VALUE: i64 = 19690720;
-
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let mut program = utils::parse_intcode_program(input)?;
-
-    for noun in 0..99 {
-        program[1] = noun;
-        for verb in 0..99 {
-            program[2] = verb;
-            let mut computer = IntcodeComputer::new(None);
-            computer.start(program.clone(), None, vec![])?;
-            let output = computer.wait_for_result()?;
-            if output == EXPECTED_VALUE {
-                return Ok((100 * noun + verb).to_string());
+impl Solution for Day02 {
+    type Input = Program;
+    type Parameters = Day02Parameters;
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "1202 Program Alarm"
+    }
+
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
+    }
+
+    fn part_one(&self, program: &Program, parameters: &Day02Parameters) -> AocResult {
+        match parameters.restore_alarm {
+            true => Ok(run_with_noun_and_verb(program.clone(), 12, 2)?.to_string()),
+            false => Ok(run_program(program.clone())?.to_string()),
+        }
+    }
+
+    fn part_two(&self, program: &Program, parameters: &Day02Parameters) -> AocResult {
+        for noun in 0..99 {
+            for verb in 0..99 {
+                if run_with_noun_and_verb(program.clone(), noun, verb)? == parameters.target {
+                    return Ok((100 * noun + verb).to_string());
+                }
             }
         }
+        Err(AocError::new(String::from("Did not reach the expected value")))
     }
-    Err(AocError::new(String::from("Did not reach the expected value")))
+}
+
+fn run_with_noun_and_verb(mut program: Program, noun: i64, verb: i64) -> Result<i64, AocError> {
+    program[1] = noun;
+    program[2] = verb;
+    run_program(program)
+}
+
+/// Returns the value at address 0 after the program halts.
+fn run_program(program: Program) -> Result<i64, AocError> {
+    let mut computer = IntcodeHardware::new(program);
+    computer.run_with_inputs(&[])?;
+    Ok(computer.read_memory(0))
 }
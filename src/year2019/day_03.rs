@@ -1,101 +1,118 @@
 use std::collections::HashMap;
 
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::Solution;
+use crate::utils::grid::{Direction, Point, SparseGrid};
+use crate::utils::input_conversion::{self, Line};
 
-pub fn first_star(input: &str) -> AocResult {
-    let mut input_lines = input.lines();
-    let wire_a = input_lines.next().map(parse_path_input).map(path_to_grid)
-        .ok_or_else(|| AocError::new(String::from("Not enough lines in the input")))?;
-    let wire_b = input_lines.next().map(parse_path_input).map(path_to_grid)
-        .ok_or_else(|| AocError::new(String::from("Not enough lines in the input")))?;
-    let intersection_grid = intersect_grids(wire_a, wire_b);
+pub struct Day03;
 
-    let mut closest_intersection = std::i32::MAX;
-    for key in intersection_grid.keys() {
-        if key == &(0, 0) {
-            continue;
-        }
-        closest_intersection = std::cmp::min(closest_intersection, manhattan_distance(&(0, 0), key));
+/// Segments of the path of a wire.
+type Wire = Vec<(Direction, i32)>;
+/// Steps each wire takes to reach the position, for every position where the wires cross.
+type Intersections = HashMap<Point, (u32, u32)>;
+
+impl Solution for Day03 {
+    type Input = (Wire, Wire);
+    type Parameters = ();
+
+    fn day(&self) -> u32 {
+        3
     }
-    Ok(closest_intersection.to_string())
-}
 
-pub fn second_star(input: &str) -> AocResult {
-    let mut input_lines = input.lines();
-    let wire_a = input_lines.next().map(parse_path_input).map(path_to_grid)
-        .ok_or_else(|| AocError::new(String::from("Not enough lines in the input")))?;
-    let wire_b = input_lines.next().map(parse_path_input).map(path_to_grid)
-        .ok_or_else(|| AocError::new(String::from("Not enough lines in the input")))?;
-    let intersection_grid = intersect_grids(wire_a, wire_b);
+    fn title(&self) -> &'static str {
+        "Crossed Wires"
+    }
 
-    let mut closest_intersection: Option<((i32, i32), u32)> = None;
-    for (position, (steps_a, steps_b)) in intersection_grid {
-        if position == (0, 0) {
-            continue;
-        }
+    fn parse(&self, input: &str) -> Result<(Wire, Wire), AocError> {
+        let mut input_lines = input_conversion::lines(input);
+        let mut next_wire = || input_lines.next().map(parse_path_input)
+            .ok_or_else(|| AocError::parse(String::from("Not enough lines in the input")))?;
+        let wire_a = next_wire()?;
+        let wire_b = next_wire()?;
+        Ok((wire_a, wire_b))
+    }
 
-        let steps_combined = steps_a + steps_b;
-        if let Some((_, best_steps)) = closest_intersection {
-            if steps_combined < best_steps {
-                closest_intersection = Some((position, steps_combined));
+    fn part_one(&self, (wire_a, wire_b): &(Wire, Wire), _parameters: &()) -> AocResult {
+        let intersection_grid = intersect_grids(path_to_grid(wire_a), path_to_grid(wire_b));
+        let mut closest_intersection = i32::MAX;
+        for key in intersection_grid.keys() {
+            if *key == Point::ORIGIN {
+                continue;
             }
-        } else {
-            closest_intersection = Some((position, steps_combined));
+            closest_intersection = std::cmp::min(closest_intersection, key.manhattan_distance(Point::ORIGIN));
         }
+        Ok(closest_intersection.to_string())
     }
 
-    if let Some((_, num_steps)) = closest_intersection {
-        Ok(num_steps.to_string())
-    } else {
-        Err(AocError::new(String::from("N/A")))
+    fn part_two(&self, (wire_a, wire_b): &(Wire, Wire), _parameters: &()) -> AocResult {
+        let intersection_grid = intersect_grids(path_to_grid(wire_a), path_to_grid(wire_b));
+        let mut closest_intersection: Option<(Point, u32)> = None;
+        for (position, (steps_a, steps_b)) in &intersection_grid {
+            if *position == Point::ORIGIN {
+                continue;
+            }
+
+            let steps_combined = steps_a + steps_b;
+            if let Some((_, best_steps)) = closest_intersection {
+                if steps_combined < best_steps {
+                    closest_intersection = Some((*position, steps_combined));
+                }
+            } else {
+                closest_intersection = Some((*position, steps_combined));
+            }
+        }
+
+        if let Some((_, num_steps)) = closest_intersection {
+            Ok(num_steps.to_string())
+        } else {
+            Err(AocError::new(String::from("N/A")))
+        }
     }
 }
 
-fn path_to_grid(path: Vec<(char, i32)>) -> HashMap<(i32, i32), u32> {
-    let mut grid = HashMap::new();
-    let mut current_position = (0, 0);
+/// Steps the wire takes to first reach each of its positions.
+fn path_to_grid(path: &[(Direction, i32)]) -> SparseGrid<u32> {
+    let mut grid = SparseGrid::new();
+    let mut current_position = Point::ORIGIN;
     let mut total_distance = 0;
 
     grid.insert(current_position, total_distance);
-    for (instruction, distance) in path {
-        for _ in 0..distance {
-            match instruction {
-                'R' => current_position.0 += 1,
-                'L' => current_position.0 -= 1,
-                'U' => current_position.1 += 1,
-                'D' => current_position.1 -= 1,
-                _ => panic!()
-            }
+    for (direction, distance) in path {
+        for _ in 0..*distance {
+            current_position = current_position.step(*direction);
             total_distance +=1;
-            grid.entry(current_position).or_insert(total_distance);
+            if grid.get(current_position).is_none() {
+                grid.insert(current_position, total_distance);
+            }
         }
     }
     grid
 }
 
-fn intersect_grids(grid_a: HashMap<(i32, i32), u32>, grid_b: HashMap<(i32, i32), u32>) -> HashMap<(i32, i32), (u32, u32)> {
+fn intersect_grids(grid_a: SparseGrid<u32>, grid_b: SparseGrid<u32>) -> Intersections {
     let mut intersection_grid = HashMap::new();
-    for key in grid_a.keys() {
-        if grid_b.contains_key(key) {
-            intersection_grid.insert(*key, (grid_a[key], grid_b[key]));
+    for (position, steps_a) in grid_a.iter() {
+        if let Some(steps_b) = grid_b.get(position) {
+            intersection_grid.insert(position, (*steps_a, *steps_b));
         }
     }
     intersection_grid
 }
 
-fn manhattan_distance(point_a: &(i32, i32), point_b: &(i32, i32)) -> i32 {
-    i32::abs(point_b.0 - point_a.0) + i32::abs(point_b.1 - point_a.1)
-}
-
-fn parse_path_input(input: &str) -> Vec<(char, i32)> {
-    let splits = input.split(',');
-    let mut output: Vec<(char, i32)> = Vec::new();
-    for split in splits {
-        let mut chars = split.chars();
-        let direction = chars.next().unwrap();
-        let distance: String = chars.collect();
-        let distance: i32 = distance.parse().unwrap();
+fn parse_path_input(line: Line) -> Result<Wire, AocError> {
+    let mut output: Vec<(Direction, i32)> = Vec::new();
+    for split in line.whole().split(',') {
+        let split = split.trim();
+        let direction = match split.text.chars().next() {
+            Some('U') => Direction::Up,
+            Some('R') => Direction::Right,
+            Some('D') => Direction::Down,
+            Some('L') => Direction::Left,
+            _ => return Err(line.error_at(split, format!("Invalid path segment '{}'", split.text))),
+        };
+        let distance: i32 = line.parse(split.slice_from(1))?;
         output.push((direction, distance));
     }
-    output
+    Ok(output)
 }
@@ -1,33 +1,41 @@
 
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::Solution;
+use crate::utils::input_conversion::{self, Line, Part};
 
 const NUMBER_LENGHT: usize = 6;
 type Number = [u32; NUMBER_LENGHT];
 
-pub fn first_star(input: &str) -> AocResult {
-    let first_line = input.lines().next().ok_or_else(
-        || AocError::new(String::from("Not enough lines in the input")))?;
+pub struct Day04;
 
-    let lower_bound: String = first_line.chars().take(6).collect();
-    let upper_bound: String = first_line.chars().skip(7).take(6).collect();
+impl Solution for Day04 {
+    /// Lower and upper bound of the range, inclusive.
+    type Input = (u32, u32);
+    type Parameters = ();
 
-    let lower_bound = to_u32(&parse_number(&lower_bound[..]));
-    let upper_bound = to_u32(&parse_number(&upper_bound[..]));
+    fn day(&self) -> u32 {
+        4
+    }
 
-    Ok(generate_numbers_with_check_function(&lower_bound, &upper_bound, check_has_double).len().to_string())
-}
+    fn title(&self) -> &'static str {
+        "Secure Container"
+    }
 
-pub fn second_star(input: &str) -> AocResult {
-    let first_line = input.lines().next().ok_or_else(
-        || AocError::new(String::from("Not enough lines in the input")))?;
+    fn parse(&self, input: &str) -> Result<(u32, u32), AocError> {
+        let first_line = input_conversion::lines(input).next().ok_or_else(
+            || AocError::parse(String::from("Not enough lines in the input")))?;
 
-    let lower_bound: String = first_line.chars().take(6).collect();
-    let upper_bound: String = first_line.chars().skip(7).take(6).collect();
+        let (lower_bound, upper_bound) = first_line.split_once(first_line.whole(), "-")?;
+        Ok((to_u32(&parse_number(first_line, lower_bound)?), to_u32(&parse_number(first_line, upper_bound)?)))
+    }
 
-    let lower_bound = to_u32(&parse_number(&lower_bound[..]));
-    let upper_bound = to_u32(&parse_number(&upper_bound[..]));
+    fn part_one(&self, (lower_bound, upper_bound): &(u32, u32), _parameters: &()) -> AocResult {
+        Ok(generate_numbers_with_check_function(lower_bound, upper_bound, check_has_double).len().to_string())
+    }
 
-    Ok(generate_numbers_with_check_function(&lower_bound, &upper_bound, check_has_isolated_double).len().to_string())
+    fn part_two(&self, (lower_bound, upper_bound): &(u32, u32), _parameters: &()) -> AocResult {
+        Ok(generate_numbers_with_check_function(lower_bound, upper_bound, check_has_isolated_double).len().to_string())
+    }
 }
 
 fn generate_numbers_with_check_function(lower_bound: &u32, upper_bound: &u32, check_function: impl Fn(&Number) -> bool) -> Vec<Number> {
@@ -42,12 +50,18 @@ fn generate_numbers_with_check_function(lower_bound: &u32, upper_bound: &u32, ch
     possible_numbers
 }
 
-fn parse_number(string_number: &str) -> Number {
+fn parse_number(line: Line, string_number: Part) -> Result<Number, AocError> {
+    let string_number = string_number.trim();
+    if string_number.text.len() != NUMBER_LENGHT {
+        return Err(line.error_at(string_number, format!("Expected a {} digit number", NUMBER_LENGHT)));
+    }
+
     let mut number = [0; 6];
-    for (idx, string_char) in string_number.chars().enumerate() {
-        number[idx] = string_char.to_digit(10).unwrap();
+    for (idx, string_char) in string_number.text.char_indices() {
+        number[idx] = string_char.to_digit(10)
+            .ok_or_else(|| line.error_at(string_number.slice_from(idx), format!("Invalid digit '{}'", string_char)))?;
     }
-    number
+    Ok(number)
 }
 
 fn check_has_double(number: &Number) -> bool {
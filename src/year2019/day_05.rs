@@ -1,44 +1,88 @@
-use std::sync::mpsc;
-
 use crate::aoc_error::{AocResult, AocError};
-use super::intcode_computer::{IntcodeComputer, utils};
+use crate::solution::{self, Parameters, Solution};
+use super::intcode_computer::{Program, utils};
+use super::intcode_computer::ascii::AsciiComputer;
+
+pub struct Day05;
+
+pub struct Day05Parameters {
+    /// ID of the system tested in the first part, the air conditioner unit.
+    pub first_system_id: i64,
+    /// ID of the system tested in the second part, the thermal radiator controller.
+    pub second_system_id: i64,
+}
+
+impl Default for Day05Parameters {
+    fn default() -> Self {
+        Day05Parameters { first_system_id: 1, second_system_id: 5 }
+    }
+}
+
+impl Parameters for Day05Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "first_system_id" => self.first_system_id = solution::parse_parameter(name, value)?,
+            "second_system_id" => self.second_system_id = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
+        }
+        Ok(())
+    }
+}
 
-pub fn first_star(input: &str) -> AocResult {
-    // Input is a single line of numbers.
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let program = utils::parse_intcode_program(input)?;
+impl Solution for Day05 {
+    type Input = Program;
+    type Parameters = Day05Parameters;
 
-    let (input_sender, input_receiver) = mpsc::channel();
-    let (output_sender, output_receiver) = mpsc::channel();
+    fn day(&self) -> u32 {
+        5
+    }
 
-    let mut computer = IntcodeComputer::new(None);
+    fn title(&self) -> &'static str {
+        "Sunny with a Chance of Asteroids"
+    }
 
-    computer.start(program, Some(input_receiver), vec![output_sender])?;
-    input_sender.send(1).map_err(|_mpsc_error| AocError::new(String::from("Could not send input")))?;
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
+    }
 
-    computer.wait_for_result()?;
-    let output = output_receiver.iter().collect::<Vec<i64>>().pop()
-                    .ok_or_else(|| AocError::new(String::from("Output buffer empty")))?;
+    fn part_one(&self, program: &Program, parameters: &Day05Parameters) -> AocResult {
+        run_diagnostic(program, parameters.first_system_id)
+    }
 
-    Ok(output.to_string())
+    fn part_two(&self, program: &Program, parameters: &Day05Parameters) -> AocResult {
+        run_diagnostic(program, parameters.second_system_id)
+    }
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    // Input is a single line of numbers.
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let program = utils::parse_intcode_program(input)?;
+fn run_diagnostic(program: &Program, system_id: i64) -> AocResult {
+    let mut computer = AsciiComputer::new(program.clone());
+    computer.send_value(system_id);
+    computer.run_to_halt()?;
+    // The diagnostic program answers with numbers, which may happen to be ASCII codes.
+    let mut outputs = computer.take_outputs();
+    let diagnostic_code = outputs.pop().ok_or_else(|| AocError::new(String::from("Did not get a diagnostic code")))?;
 
-    let (input_sender, input_receiver) = mpsc::channel();
-    let (output_sender, output_receiver) = mpsc::channel();
+    // Passed tests output 0, failed ones the difference from the expected value.
+    let failed_tests = outputs.iter().filter(|output| **output != 0).count();
+    if failed_tests > 0 {
+        return Err(AocError::new(format!("{} diagnostic tests failed", failed_tests)));
+    }
 
-    let mut computer = IntcodeComputer::new(None);
+    Ok(diagnostic_code.to_string())
+}
 
-    computer.start(program, Some(input_receiver), vec![output_sender])?;
-    input_sender.send(5).map_err(|_mpsc_error| AocError::new(String::from("Could not send input")))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    computer.wait_for_result()?;
-    let output = output_receiver.iter().collect::<Vec<i64>>().pop()
-                    .ok_or_else(|| AocError::new(String::from("Output buffer empty")))?;
+    #[test]
+    fn test_run_diagnostic() {
+        // Outputs 1 if the input is equal to 8, 0 otherwise.
+        let program = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run_diagnostic(&program, 8).unwrap(), "1");
+        assert_eq!(run_diagnostic(&program, 7).unwrap(), "0");
 
-    Ok(output.to_string())
-}
\ No newline at end of file
+        // A failed test with a large offset, followed by the diagnostic code.
+        assert!(run_diagnostic(&vec![104, 200, 104, 5, 99], 1).is_err());
+    }
+}
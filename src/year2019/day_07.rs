@@ -1,119 +1,63 @@
-use std::sync::{Arc, Barrier, mpsc};
-
 use crate::aoc_error::{AocResult, AocError};
-use super::intcode_computer::{IntcodeComputer, utils};
+use crate::solution::Solution;
+use super::intcode_computer::{Program, utils};
+use super::intcode_computer::network::{Network, NetworkState, Topology};
 
 use permutohedron::heap_recursive;
 
-// TODO: get rid of unwraps.
-pub fn first_star(input: &str) -> AocResult {
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let program = utils::parse_intcode_program(input)?;
-
-    let mut phase_setting = [0, 1, 2, 3, 4];
-    let mut permutations = vec!();
-    heap_recursive(&mut phase_setting, |permutation| { permutations.push(permutation.to_vec()) });
-
-    let mut outputs = vec![0; permutations.len()];
-    for permutation in permutations {
+const NUM_AMPLIFIERS: usize = 5;
 
-        let mut senders = vec![];
-        let mut receivers = vec![];
-        for _ in 0..5 {
-            let (sx, rx) = mpsc::channel();
-            senders.push(sx);
-            receivers.push(rx);
-        }
+pub struct Day07;
 
-        // Send in the initial configuration and signal.
-        for i in 0..5 {
-            senders[i].send(permutation[i]).map_err(|_mpsc_error| AocError::new(String::from("Could not input configuration")))?;
-        }
-        senders[0].send(0).map_err(|_mpsc_error| AocError::new(String::from("Could not input the initial signal")))?;
+impl Solution for Day07 {
+    type Input = Program;
+    type Parameters = ();
 
-        let sync_barrier = Arc::new(Barrier::new(6));
-        let mut amp_computers = [
-            IntcodeComputer::new(Some(sync_barrier.clone())), IntcodeComputer::new(Some(sync_barrier.clone())),
-            IntcodeComputer::new(Some(sync_barrier.clone())), IntcodeComputer::new(Some(sync_barrier.clone())),
-            IntcodeComputer::new(Some(sync_barrier.clone())),
-        ];
+    fn day(&self) -> u32 {
+        7
+    }
 
-        // Shift senders back by one, so that the sending computer is the one before the receiving.
-        senders.rotate_left(1);
+    fn title(&self) -> &'static str {
+        "Amplification Circuit"
+    }
 
-        let mut sx_rx: Vec<(mpsc::Sender<i64>, mpsc::Receiver<i64>)> = senders.drain(..).zip(receivers.drain(..)).rev().collect();
-        for computer in amp_computers.iter_mut().take(4) {
-            let (sender, receiver) = sx_rx.pop().unwrap();
-            computer.start(program.clone(), Some(receiver), vec![sender])?;
-        }
-        let (_, receiver) = sx_rx.pop().unwrap();
-        let (output_sender, output_receiver) = mpsc::channel();
-        amp_computers[4].start(program.clone(), Some(receiver), vec![output_sender])?;
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
+    }
 
-        sync_barrier.wait();
+    fn part_one(&self, program: &Program, _parameters: &()) -> AocResult {
+        max_thruster_signal(program, [0, 1, 2, 3, 4], Topology::Pipeline)
+    }
 
-        for computer in &mut amp_computers {
-            computer.wait_for_result()?;
-        }
-        outputs.push(output_receiver.recv().map_err(|_mpsc_error| AocError::new(String::from("Did not receive output")))?);
+    fn part_two(&self, program: &Program, _parameters: &()) -> AocResult {
+        max_thruster_signal(program, [5, 6, 7, 8, 9], Topology::Ring)
     }
-    let maximum_output = outputs.iter().max().ok_or_else(|| AocError::new(String::from("Could not get maximum value")))?;
-    Ok(maximum_output.to_string())
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let program = utils::parse_intcode_program(input)?;
-
-    let mut phase_setting = [5, 6, 7, 8, 9];
+/// Highest signal the last amplifier sends to the thrusters over all the orders of the phase settings.
+fn max_thruster_signal(program: &Program, mut phase_settings: [i64; NUM_AMPLIFIERS], topology: Topology) -> AocResult {
     let mut permutations = vec!();
-    heap_recursive(&mut phase_setting, |permutation| { permutations.push(permutation.to_vec()) });
+    heap_recursive(&mut phase_settings, |permutation| { permutations.push(permutation.to_vec()) });
 
-    let mut outputs = vec![0; permutations.len()];
+    let mut signals = vec![];
     for permutation in permutations {
+        signals.push(run_amplifiers(program, &permutation, topology)?);
+    }
+    let maximum_signal = signals.iter().max().ok_or_else(|| AocError::new(String::from("Could not get maximum value")))?;
+    Ok(maximum_signal.to_string())
+}
 
-        let mut senders = vec![];
-        let mut receivers = vec![];
-        for _ in 0..5 {
-            let (sx, rx) = mpsc::channel();
-            senders.push(sx);
-            receivers.push(rx);
-        }
-
-        // Send in the initial configuration and signal.
-        for i in 0..5 {
-            senders[i].send(permutation[i]).map_err(|_mpsc_error| AocError::new(String::from("Could not input configuration")))?;
-        }
-        senders[0].send(0).map_err(|_mpsc_error| AocError::new(String::from("Could not input the initial signal")))?;
-
-        let sync_barrier = Arc::new(Barrier::new(6));
-        let mut amp_computers = [
-            IntcodeComputer::new(Some(sync_barrier.clone())), IntcodeComputer::new(Some(sync_barrier.clone())),
-            IntcodeComputer::new(Some(sync_barrier.clone())), IntcodeComputer::new(Some(sync_barrier.clone())),
-            IntcodeComputer::new(Some(sync_barrier.clone())),
-        ];
-
-        // Shift senders back by one, so that the sending computer is the one before the receiving.
-        senders.rotate_left(1);
-
-        let mut sx_rx: Vec<(mpsc::Sender<i64>, mpsc::Receiver<i64>)> = senders.drain(..).zip(receivers.drain(..)).rev().collect();
-        for computer in amp_computers.iter_mut().take(4) {
-            let (sender, receiver) = sx_rx.pop().unwrap();
-            computer.start(program.clone(), Some(receiver), vec![sender])?;
-        }
-        let (sender, receiver) = sx_rx.pop().unwrap();
-        let (output_sender, output_receiver) = mpsc::channel();
-        amp_computers[4].start(program.clone(), Some(receiver), vec![sender, output_sender])?;
-
-        sync_barrier.wait();
-
-        for computer in &mut amp_computers {
-            computer.wait_for_result()?;
-        }
+fn run_amplifiers(program: &Program, phase_settings: &[i64], topology: Topology) -> Result<i64, AocError> {
+    let mut amplifiers = Network::with_copies(program, phase_settings.len(), topology);
+    for (amplifier, phase_setting) in phase_settings.iter().enumerate() {
+        amplifiers.send(amplifier, *phase_setting)?;
+    }
+    // The initial input signal.
+    amplifiers.send(0, 0)?;
 
-        let result = output_receiver.iter().last();
-        outputs.push(result.ok_or_else(|| AocError::new(String::from("Did not get output")))?);
+    match amplifiers.run()? {
+        NetworkState::Halted => (),
+        state => return Err(AocError::new(format!("Amplifiers did not finish: {:?}", state))),
     }
-    let maximum_output = outputs.iter().max().ok_or_else(|| AocError::new(String::from("Could not get maximum value")))?;
-    Ok(maximum_output.to_string())
+    amplifiers.outputs().last().copied().ok_or_else(|| AocError::new(String::from("Did not get output")))
 }
@@ -1,45 +1,99 @@
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::{self, AnswerType, Parameters, Solution};
+use crate::utils::grid::DenseGrid;
 use crate::utils::input_conversion;
 
-const IMAGE_SIZE: (usize, usize) = (25, 6);
+pub struct Day08;
 
-pub fn first_star(input: &str) -> AocResult {
-    let input: Vec<String> = input_conversion::input_to_lines(input).map_err(|_err| AocError::new(String::from("Could not get input lines")))?;
-    let input_image = input[0].chars().map(|ch| ch.to_digit(10)
-                        .ok_or_else(|| AocError::new(String::from("Could not convert input to u32 image"))))
-                        .collect::<Result<Vec<u32>, AocError>>()?;
-    let mut processed_layers = input_image.chunks_exact(IMAGE_SIZE.0 * IMAGE_SIZE.1).map(analyze_layer).collect::<Vec<(u32, u32)>>();
-    processed_layers.sort_unstable();
-    Ok(processed_layers[0].1.to_string())
+pub struct Day08Parameters {
+    pub width: usize,
+    pub height: usize,
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    let input: Vec<String> = input_conversion::input_to_lines(input).map_err(|_err| AocError::new(String::from("Could not get input lines")))?;
-    let input_image = input[0].chars().map(|ch| ch.to_digit(10)
-                        .ok_or_else(|| AocError::new(String::from("Could not convert input to u32 image"))))
-                        .collect::<Result<Vec<u32>, AocError>>()?;
-    let mut output_image: Vec<u32> = vec![2; IMAGE_SIZE.0 * IMAGE_SIZE.1];
-    for chunk in input_image.chunks_exact(IMAGE_SIZE.0 * IMAGE_SIZE.1) {
-        for (layer_pixel, composite_pixel) in chunk.iter().zip(output_image.iter_mut()) {
-            if *composite_pixel == 2 {
-                *composite_pixel = *layer_pixel;
-            }
+impl Default for Day08Parameters {
+    fn default() -> Self {
+        Day08Parameters { width: 25, height: 6 }
+    }
+}
+
+impl Parameters for Day08Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "width" => self.width = solution::parse_parameter(name, value)?,
+            "height" => self.height = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
         }
+        if self.width == 0 || self.height == 0 {
+            return Err(AocError::InvalidArgument(String::from("The image size must not be zero")));
+        }
+        Ok(())
+    }
+}
+
+impl Solution for Day08 {
+    /// Pixels of all the layers.
+    type Input = Vec<u32>;
+    type Parameters = Day08Parameters;
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Image Format"
+    }
+
+    fn answer_types(&self) -> (AnswerType, AnswerType) {
+        (AnswerType::Number, AnswerType::Text)
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>, AocError> {
+        let first_line = input_conversion::lines(input).next()
+            .ok_or_else(|| AocError::parse(String::from("Empty input")))?;
+        first_line.text.char_indices().map(|(index, ch)| ch.to_digit(10)
+            .ok_or_else(|| first_line.error_at(first_line.whole().slice_from(index), format!("Invalid pixel '{}'", ch))))
+            .collect::<Result<Vec<u32>, AocError>>()
     }
 
-    let mut printable_output_image = String::new();
-    for (idx, output_pixel) in output_image.iter().enumerate() {
-        if idx % IMAGE_SIZE.0 == 0 {
-            printable_output_image.push('\n');
+    fn part_one(&self, input_image: &Vec<u32>, image_size: &Day08Parameters) -> AocResult {
+        let mut processed_layers = layers(input_image, image_size)?.map(analyze_layer).collect::<Vec<(u32, u32)>>();
+        processed_layers.sort_unstable();
+        let (_, checksum) = processed_layers.first()
+            .ok_or_else(|| AocError::new(String::from("The image has no complete layer")))?;
+        Ok(checksum.to_string())
+    }
+
+    fn part_two(&self, input_image: &Vec<u32>, image_size: &Day08Parameters) -> AocResult {
+        let mut output_image = DenseGrid::new(image_size.width, image_size.height, 2);
+        let mut layers = layers(input_image, image_size)?.peekable();
+        if layers.peek().is_none() {
+            return Err(AocError::new(String::from("The image has no complete layer")));
+        }
+        for chunk in layers {
+            for (layer_pixel, composite_pixel) in chunk.iter().zip(output_image.iter_mut()) {
+                if *composite_pixel == 2 {
+                    *composite_pixel = *layer_pixel;
+                }
+            }
         }
-        let printable_pixel = match *output_pixel {
+
+        if output_image.iter().any(|(_, pixel)| *pixel > 1) {
+            return Err(AocError::new(String::from("Unexpected signal in the image")));
+        }
+        let printable_output_image = format!("\n{}", output_image.render(|pixel| match pixel {
             0 => '⬛',
-            1 => '⬜',
-            _ => return Err(AocError::new(String::from("Unexpected signal in the image"))),
-        };
-        printable_output_image.push(printable_pixel);
+            _ => '⬜',
+        }));
+        Ok(printable_output_image)
+    }
+}
+
+/// Complete layers of the image, a trailing incomplete layer is ignored.
+fn layers<'a>(input_image: &'a [u32], image_size: &Day08Parameters) -> Result<std::slice::ChunksExact<'a, u32>, AocError> {
+    match image_size.width.checked_mul(image_size.height) {
+        Some(layer_size) if layer_size > 0 => Ok(input_image.chunks_exact(layer_size)),
+        _ => Err(AocError::InvalidArgument(format!("Invalid image size {}x{}", image_size.width, image_size.height))),
     }
-    Ok(printable_output_image)
 }
 
 /// Returns (number of zeros, number of ones * number of twos) for the given image layer
@@ -62,3 +116,18 @@ fn analyze_layer(layer: &[u32]) -> (u32, u32) {
     }
     (zeros_count, ones_count * twos_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_size() {
+        let image = vec![0, 0, 1, 1, 2, 2];
+        assert_eq!(Day08.part_one(&image, &Day08Parameters { width: 3, height: 1 }).unwrap(), "2");
+        assert!(Day08.part_one(&image, &Day08Parameters { width: 1000, height: 1000 }).is_err());
+        assert!(Day08.part_two(&image, &Day08Parameters { width: 1000, height: 1000 }).is_err());
+        assert!(Day08.part_one(&image, &Day08Parameters { width: 0, height: 6 }).is_err());
+        assert!(Day08.parse("").is_err());
+    }
+}
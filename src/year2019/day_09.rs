@@ -1,29 +1,78 @@
-use std::sync::mpsc;
-
 use crate::aoc_error::{AocError, AocResult};
-use super::intcode_computer::{IntcodeComputer, utils};
+use crate::solution::{self, Parameters, Solution};
+use super::intcode_computer::{Program, utils};
+use super::intcode_computer::ascii::AsciiComputer;
+
+pub struct Day09;
+
+pub struct Day09Parameters {
+    /// Input of the first part, 1 runs the BOOST program in the test mode.
+    pub first_input: i64,
+    /// Input of the second part, 2 runs the BOOST program in the sensor boost mode.
+    pub second_input: i64,
+}
 
+impl Default for Day09Parameters {
+    fn default() -> Self {
+        Day09Parameters { first_input: 1, second_input: 2 }
+    }
+}
 
-pub fn first_star(input: &str) -> AocResult {
-    Ok(run_with_input(input, 1)?)
+impl Parameters for Day09Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "first_input" => self.first_input = solution::parse_parameter(name, value)?,
+            "second_input" => self.second_input = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
+        }
+        Ok(())
+    }
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    Ok(run_with_input(input, 2)?)
+impl Solution for Day09 {
+    type Input = Program;
+    type Parameters = Day09Parameters;
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Sensor Boost"
+    }
+
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
+    }
+
+    fn part_one(&self, program: &Program, parameters: &Day09Parameters) -> AocResult {
+        run_with_input(program, parameters.first_input)
+    }
+
+    fn part_two(&self, program: &Program, parameters: &Day09Parameters) -> AocResult {
+        run_with_input(program, parameters.second_input)
+    }
 }
 
-fn run_with_input(input: &str, computer_input: i64) -> AocResult {
-    let input = input.lines().next().ok_or_else(|| AocError::new(String::from("Could not parse a line")))?;
-    let program = utils::parse_intcode_program(input)?;
+fn run_with_input(program: &Program, computer_input: i64) -> AocResult {
+    let mut computer = AsciiComputer::new(program.clone());
+    computer.send_value(computer_input);
+    computer.run_to_halt()?;
 
-    let (input_sender, input_receiver) = mpsc::channel();
-    let (output_sender, output_receiver) = mpsc::channel();
-    let mut computer = IntcodeComputer::new(None);
+    // A malfunctioning BOOST program lists the opcodes which don't work instead of the keycode.
+    match computer.take_outputs().as_slice() {
+        [output] => Ok(output.to_string()),
+        outputs => Err(AocError::new(format!("Expected a single keycode, got {:?}", outputs))),
+    }
+}
 
-    computer.start(program, Some(input_receiver), vec![output_sender])?;
-    input_sender.send(computer_input).map_err(|_mpsc_error| AocError::new(String::from("Could not send input")))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    computer.wait_for_result()?;
-    let output = output_receiver.iter().next().ok_or_else(|| AocError::new(String::from("Did not get output")))?;
-    Ok(output.to_string())
-}
\ No newline at end of file
+    #[test]
+    fn test_run_with_input() {
+        assert_eq!(run_with_input(&vec![104, 50, 99], 1).unwrap(), "50");
+        assert!(run_with_input(&vec![104, 50, 104, 0, 99], 1).is_err());
+    }
+}
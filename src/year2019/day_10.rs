@@ -4,58 +4,102 @@ use std::collections::{HashMap, HashSet};
 use num::integer;
 
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::{self, Parameters, Solution};
+use crate::utils::grid::Point;
+use crate::utils::input_conversion;
 
-pub fn first_star(input: &str) -> AocResult {
-    let asteroid_map = convert_input_to_asteroid_map(input)?;
-    let visible_asteroids_map = calculate_asteroid_visibility(&asteroid_map);
-    let max_visible_asteroids = visible_asteroids_map.values().map(HashMap::len).max()
-                                    .ok_or_else(|| AocError::new(String::from("Invalid station position")))?;
-    Ok(max_visible_asteroids.to_string())
+pub struct Day10;
+
+pub struct Day10Parameters {
+    /// The second part looks for the asteroid vaporized as this one, counting from 1.
+    pub vaporized: usize,
+}
+
+impl Default for Day10Parameters {
+    fn default() -> Self {
+        Day10Parameters { vaporized: 200 }
+    }
 }
 
-pub fn second_star(input: &str) -> AocResult {
-    // Find the position of the station.
-    let mut asteroid_map = convert_input_to_asteroid_map(input)?;
-    let visible_asteroids_map = calculate_asteroid_visibility(&asteroid_map);
-    let (station_position, _) = visible_asteroids_map.iter().max_by_key(|(_, visible_asteroids)| visible_asteroids.len())
-        .ok_or_else(|| AocError::new(String::from("Invalid station position")))?;
-
-    // Shoot them asteroids.
-    // let station_position = &(8, 3);
-    let mut destroyed_targets = 0;
-    let mut targets = acquire_targets(station_position, &asteroid_map);
-    let last_destroyed_asteroid_position = loop {
-        if targets.is_empty() {
-            targets = acquire_targets(station_position, &asteroid_map)
+impl Parameters for Day10Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "vaporized" => self.vaporized = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
+        }
+        if self.vaporized == 0 {
+            return Err(AocError::InvalidArgument(String::from("The vaporized asteroids are counted from 1")));
         }
+        Ok(())
+    }
+}
+
+impl Solution for Day10 {
+    type Input = AsteroidMap;
+    type Parameters = Day10Parameters;
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Monitoring Station"
+    }
 
-        match targets.pop() {
-            Some(position) => {
-                destroyed_targets += 1;
-                asteroid_map.remove(&position);
-                if destroyed_targets == 200 {
-                    break Ok(position)
-                }
-            },
-            None => break Err(AocError::new(String::from("Got invalid target")))
-        };
-    }?;
-    Ok((last_destroyed_asteroid_position.0 * 100 + last_destroyed_asteroid_position.1).to_string())
+    fn parse(&self, input: &str) -> Result<AsteroidMap, AocError> {
+        convert_input_to_asteroid_map(input)
+    }
+
+    fn part_one(&self, asteroid_map: &AsteroidMap, _parameters: &Day10Parameters) -> AocResult {
+        let visible_asteroids_map = calculate_asteroid_visibility(asteroid_map);
+        let max_visible_asteroids = visible_asteroids_map.values().map(HashMap::len).max()
+                                        .ok_or_else(|| AocError::new(String::from("Invalid station position")))?;
+        Ok(max_visible_asteroids.to_string())
+    }
+
+    fn part_two(&self, asteroid_map: &AsteroidMap, parameters: &Day10Parameters) -> AocResult {
+        // Find the position of the station.
+        let mut asteroid_map = asteroid_map.clone();
+        let visible_asteroids_map = calculate_asteroid_visibility(&asteroid_map);
+        let (station_position, _) = visible_asteroids_map.iter().max_by_key(|(_, visible_asteroids)| visible_asteroids.len())
+            .ok_or_else(|| AocError::new(String::from("Invalid station position")))?;
+
+        // Shoot them asteroids.
+        let mut destroyed_targets = 0;
+        let mut targets = acquire_targets(station_position, &asteroid_map);
+        let last_destroyed_asteroid_position = loop {
+            if targets.is_empty() {
+                targets = acquire_targets(station_position, &asteroid_map)
+            }
+
+            match targets.pop() {
+                Some(position) => {
+                    destroyed_targets += 1;
+                    asteroid_map.remove(&position);
+                    if destroyed_targets == parameters.vaporized {
+                        break Ok(position)
+                    }
+                },
+                None => break Err(AocError::new(format!("Only {} asteroids can be vaporized, not {}",
+                                                        destroyed_targets, parameters.vaporized))),
+            };
+        }?;
+        Ok((last_destroyed_asteroid_position.x * 100 + last_destroyed_asteroid_position.y).to_string())
+    }
 }
 
 const ASTEROID: char = '#';
 
-type SpaceCoordinate = (i32, i32);
-type Direction = (i32, i32);
-type AsteroidMap = HashSet<SpaceCoordinate>;
-type VisibilityMap = HashMap<SpaceCoordinate, HashMap<Direction, SpaceCoordinate>>;
+type AsteroidMap = HashSet<Point>;
+/// Closest visible asteroid in every direction, for every asteroid.
+type VisibilityMap = HashMap<Point, HashMap<Point, Point>>;
 
 fn convert_input_to_asteroid_map(input: &str) -> Result<AsteroidMap, AocError> {
     let mut asteroid_map = AsteroidMap::new();
-    for (idx_y, line) in input.lines().enumerate() {
-        for (idx_x, asteroid_reading) in line.chars().enumerate() {
-            if asteroid_reading == ASTEROID {
-                asteroid_map.insert((idx_x as i32, idx_y as i32));
+    for (idx_y, row) in input_conversion::char_grid(input)?.iter().enumerate() {
+        for (idx_x, asteroid_reading) in row.iter().enumerate() {
+            if *asteroid_reading == ASTEROID {
+                asteroid_map.insert(Point::new(idx_x as i32, idx_y as i32));
             }
         }
     }
@@ -70,16 +114,14 @@ fn calculate_asteroid_visibility(asteroid_map: &AsteroidMap) -> VisibilityMap {
                 continue;
             }
 
-            let direction = (target_position.0 - source_position.0,
-                             target_position.1 - source_position.1);
+            let direction = *target_position - *source_position;
             visible_asteroids_map.entry(source_position.to_owned()).or_insert_with(HashMap::new)
                 .entry(normalize_direction(direction))
                 // Keep only the closest asteroid's position in the given direction.
-                .and_modify(|existing_position: &mut (i32, i32)| {
-                    let existing_distance = (existing_position.0 - source_position.0).abs() + (existing_position.1 - source_position.1).abs();
-                    if existing_distance > direction.0.abs() + direction.1.abs() {
-                        existing_position.0 = target_position.0;
-                        existing_position.1 = target_position.1;
+                .and_modify(|existing_position: &mut Point| {
+                    let existing_distance = existing_position.manhattan_distance(*source_position);
+                    if existing_distance > direction.manhattan_distance(Point::ORIGIN) {
+                        *existing_position = *target_position;
                     }
                 })
                 .or_insert_with(|| target_position.to_owned());
@@ -88,12 +130,13 @@ fn calculate_asteroid_visibility(asteroid_map: &AsteroidMap) -> VisibilityMap {
     visible_asteroids_map
 }
 
-fn acquire_targets(station_position: &SpaceCoordinate, asteroid_map: &AsteroidMap) -> Vec<SpaceCoordinate> {
-    let mut visible_asteroids = calculate_asteroid_visibility(asteroid_map)[station_position]
-                                    .values().cloned().collect::<Vec<SpaceCoordinate>>();
+fn acquire_targets(station_position: &Point, asteroid_map: &AsteroidMap) -> Vec<Point> {
+    // The station has no entry once it has no asteroids left to see.
+    let mut visible_asteroids = calculate_asteroid_visibility(asteroid_map).get(station_position)
+                                    .map_or_else(Vec::new, |visible| visible.values().cloned().collect::<Vec<Point>>());
     visible_asteroids.sort_by(|a, b| {
-        let a_station_asteroid_direction = (a.0 - station_position.0, a.1 - station_position.1);
-        let b_station_asteroid_direciton = (b.0 - station_position.0, b.1 - station_position.1);
+        let a_station_asteroid_direction = *a - *station_position;
+        let b_station_asteroid_direciton = *b - *station_position;
         clockwise_angle(a_station_asteroid_direction)
             .partial_cmp(&clockwise_angle(b_station_asteroid_direciton)).unwrap_or(cmp::Ordering::Equal)
     });
@@ -101,22 +144,22 @@ fn acquire_targets(station_position: &SpaceCoordinate, asteroid_map: &AsteroidMa
     visible_asteroids
 }
 
-fn normalize_direction(direction: SpaceCoordinate) -> SpaceCoordinate {
-    let gcd = integer::gcd(direction.0, direction.1);
+fn normalize_direction(direction: Point) -> Point {
+    let gcd = integer::gcd(direction.x, direction.y);
     if gcd == 0 {
         return direction;
     }
-    (direction.0 / gcd, direction.1 / gcd)
+    Point::new(direction.x / gcd, direction.y / gcd)
 }
 
-fn clockwise_angle(direction_vector: SpaceCoordinate) -> f32 {
+fn clockwise_angle(direction_vector: Point) -> f32 {
     // Hacky hack is hacky so that 0. is returned for (0, y).
-    if direction_vector.0 == 0 {
+    if direction_vector.x == 0 {
         return 0.
     }
 
-    let norm = (direction_vector.0.abs() + direction_vector.1.abs()) as f32;
-    let normalized_vector = (direction_vector.0 as f32 / norm, direction_vector.1 as f32 / norm);
+    let norm = direction_vector.manhattan_distance(Point::ORIGIN) as f32;
+    let normalized_vector = (direction_vector.x as f32 / norm, direction_vector.y as f32 / norm);
     let mut angle = normalized_vector.1.atan2(normalized_vector.0) + std::f32::consts::PI / 2.;
 
     if angle < 0. {
@@ -132,10 +175,24 @@ mod tests {
 
     #[test]
     fn test_clockwise_angle() {
-        assert_approx_eq!(clockwise_angle((0, 1)), 0.);
-        assert_approx_eq!(clockwise_angle((1, 0)), std::f32::consts::PI / 2.);
-        assert_approx_eq!(clockwise_angle((2, 2)), std::f32::consts::PI * (3./4.));
-        assert_approx_eq!(clockwise_angle((-2, 2)), std::f32::consts::PI * (5./4.));
-        assert_approx_eq!(clockwise_angle((-2, 0)), std::f32::consts::PI * (3./2.));
+        assert_approx_eq!(clockwise_angle(Point::new(0, 1)), 0.);
+        assert_approx_eq!(clockwise_angle(Point::new(1, 0)), std::f32::consts::PI / 2.);
+        assert_approx_eq!(clockwise_angle(Point::new(2, 2)), std::f32::consts::PI * (3./4.));
+        assert_approx_eq!(clockwise_angle(Point::new(-2, 2)), std::f32::consts::PI * (5./4.));
+        assert_approx_eq!(clockwise_angle(Point::new(-2, 0)), std::f32::consts::PI * (3./2.));
+    }
+
+    #[test]
+    fn test_fewer_asteroids_than_vaporized() {
+        let asteroid_map = Day10.parse("###\n").unwrap();
+        let mut parameters = Day10Parameters::default();
+        parameters.set("vaporized", "2").unwrap();
+        assert_eq!(Day10.part_two(&asteroid_map, &parameters).unwrap(), "0");
+        parameters.set("vaporized", "3").unwrap();
+        assert!(Day10.part_two(&asteroid_map, &parameters).is_err());
+        assert!(parameters.set("vaporized", "0").is_err());
+
+        let asteroid_map = Day10.parse("#.\n.#\n").unwrap();
+        assert!(Day10.part_two(&asteroid_map, &Day10Parameters::default()).is_err());
     }
 }
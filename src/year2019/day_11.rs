@@ -1,90 +1,76 @@
-use std::collections::HashMap;
-use std::sync::mpsc;
+use std::cell::RefCell;
 
 use crate::aoc_error::{AocError, AocResult};
-use super::intcode_computer::{IntcodeComputer, utils};
+use crate::solution::{AnswerType, Solution};
+use crate::utils::grid::{Direction, Point, SparseGrid};
+use super::intcode_computer::{IntcodeComputerError, IntcodeHardware, Program, utils};
+use super::intcode_computer::devices::{FnInput, FramedOutput};
 
-fn run_robot_with_initial_tile(input: &str, initial_tile: i64) -> Result<HashMap<(i32, i32), i64>, AocError>{
-    let mut robot_direction = (0, 1);
-    let mut robot_position = (0, 0);
-    let mut colored_positions = HashMap::new();
+fn run_robot_with_initial_tile(program: &Program, initial_tile: i64) -> Result<SparseGrid<i64>, AocError>{
+    let robot = RefCell::new((Point::ORIGIN, Direction::Up));
+    let colored_positions = RefCell::new(SparseGrid::new());
 
-    let program = utils::parse_intcode_program(input)?;
-    let mut brain = IntcodeComputer::new(None);
+    // The camera sees the color under the robot, the brain answers with a color to paint and a turn.
+    let mut camera = FnInput(|| {
+        let position = robot.borrow().0;
+        let default_color = if position == Point::ORIGIN { initial_tile } else { 0 };
+        Some(*colored_positions.borrow().get(position).unwrap_or(&default_color))
+    });
+    let mut wheels = FramedOutput::new(|[paint_instruction, direction_instruction]: [i64; 2]| {
+        let (position, direction) = &mut *robot.borrow_mut();
+        match paint_instruction {
+            0 | 1 => colored_positions.borrow_mut().insert(*position, paint_instruction),
+            _ => return Err(IntcodeComputerError::new(String::from("Invalid paint instruction"))),
+        };
+        *direction = match direction_instruction {
+            1 => direction.turn_right(),
+            0 => direction.turn_left(),
+            _ => return Err(IntcodeComputerError::new(String::from("Invalid direction change"))),
+        };
+        *position = position.step(*direction);
+        Ok(())
+    });
 
-    let (input_sender, input_receiver) = mpsc::channel();
-    let (output_sender, output_receiver) = mpsc::channel();
+    let mut brain = IntcodeHardware::new(program.clone());
+    brain.run_with_devices(&mut camera, &mut wheels)?;
+    wheels.finish()?;
+    drop((camera, wheels));
+    Ok(colored_positions.into_inner())
+}
 
-    brain.start(program, Some(input_receiver), vec![output_sender])?;
+pub struct Day11;
 
-    input_sender.send(initial_tile).map_err(|err| AocError::new(format!("Could not send input: {}", err)))?;
-    while let Ok(paint_instruction) = output_receiver.recv() {
-        match paint_instruction {
-            0 => colored_positions.insert(robot_position, 0),
-            1 => colored_positions.insert(robot_position, 1),
-            _ => return Err(AocError::new(String::from("Invalid paint instruction")))
-        };
+impl Solution for Day11 {
+    type Input = Program;
+    type Parameters = ();
 
-        if let Ok(direction_instruction) = output_receiver.recv() {
-            robot_direction = match direction_instruction {
-                1 => {
-                    // Turn right.
-                    match robot_direction {
-                        (0, 1) => Ok((1, 0)),
-                        (1, 0) => Ok((0, -1)),
-                        (0, -1) => Ok((-1, 0)),
-                        (-1, 0) => Ok((0, 1)),
-                        _ => Err(AocError::new(String::from("Invalid direction")))
-                    }
-                },
-                0 => {
-                    // Turn left.
-                    match robot_direction {
-                        (0, 1) => Ok((-1, 0)),
-                        (-1, 0) => Ok((0, -1)),
-                        (0, -1) => Ok((1, 0)),
-                        (1, 0) => Ok((0, 1)),
-                        _ => Err(AocError::new(String::from("Invalid direction")))
-                    }
-                },
-                _ => Err(AocError::new(String::from("Invalid direction change")))
-            }?;
+    fn day(&self) -> u32 {
+        11
+    }
 
-            robot_position.0 += robot_direction.0;
-            robot_position.1 += robot_direction.1;
-        } else {
-            return Err(AocError::new(String::from("Brain terminated unexpectedly when moving")));
-        }
+    fn title(&self) -> &'static str {
+        "Space Police"
+    }
 
-        let color = colored_positions.get(&robot_position).unwrap_or(&0);
-        input_sender.send(color.to_owned())
-                    .map_err(|err| AocError::new(format!("Brain terminated when receiveing input: {}", err)))?;
+    fn answer_types(&self) -> (AnswerType, AnswerType) {
+        (AnswerType::Number, AnswerType::Text)
     }
-    Ok(colored_positions)
-}
 
-pub fn first_star(input: &str) -> AocResult {
-    let colored_positions = run_robot_with_initial_tile(input, 0)?;
-    Ok(colored_positions.len().to_string())
-}
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
+    }
 
-pub fn second_star(input: &str) -> AocResult {
-    let colored_positions = run_robot_with_initial_tile(input, 1)?;
-    let mut x_positions = colored_positions.keys().map(|position| position.0).collect::<Vec<i32>>();
-    let mut y_positions = colored_positions.keys().map(|position| position.1).collect::<Vec<i32>>();
-    x_positions.sort_unstable();
-    y_positions.sort_unstable();
+    fn part_one(&self, program: &Program, _parameters: &()) -> AocResult {
+        let colored_positions = run_robot_with_initial_tile(program, 0)?;
+        Ok(colored_positions.len().to_string())
+    }
 
-    let mut output_string = String::from("\n");
-    for y in (y_positions[0]..=y_positions[y_positions.len() - 1]).rev() {
-        for x in x_positions[0]..=x_positions[x_positions.len() - 1] {
-            let mut color = '.';
-            if colored_positions.get(&(x, y)).unwrap_or(&0) == &1 {
-                color = '#'
-            }
-            output_string.push(color);
-        }
-        output_string.push('\n')
+    fn part_two(&self, program: &Program, _parameters: &()) -> AocResult {
+        let colored_positions = run_robot_with_initial_tile(program, 1)?;
+        let output_string = format!("\n{}", colored_positions.render(|color| match color {
+            Some(1) => '#',
+            _ => '.',
+        }));
+        Ok(output_string)
     }
-    Ok(output_string)
 }
@@ -1,38 +1,293 @@
-use std::collections::HashMap;
-use std::sync::mpsc;
+use std::io::{Read, Write};
 
 use crate::aoc_error::{AocError, AocResult};
-use super::intcode_computer::{IntcodeComputer, utils};
+use crate::solution::Solution;
+use crate::utils::grid::{Point, SparseGrid};
+use super::intcode_computer::{IntcodeComputerError, IntcodeHardware, Program, RunState, utils};
+use super::intcode_computer::devices::{FnInput, FramedOutput};
 
+const TILE_EMPTY: i64 = 0;
+const TILE_WALL: i64 = 1;
 const TILE_BLOCK: i64 = 2;
+const TILE_PADDLE: i64 = 3;
+const TILE_BALL: i64 = 4;
 
+/// Output position which carries the score instead of a tile.
+const SCORE_POSITION: Point = Point { x: -1, y: 0 };
+/// Number of quarters which makes the cabinet play for free.
+const FREE_PLAY: i64 = 2;
 
-pub fn first_star(input: &str) -> AocResult {
-    let program = utils::parse_intcode_program(input)?;
-    let mut cabinet = IntcodeComputer::new(None);
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
 
-    let mut tiles = HashMap::new();
+impl Joystick {
+    fn value(self) -> i64 {
+        match self {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArcadeState {
+    /// The game waits for the next position of the joystick.
+    WaitingForJoystick,
+    GameOver,
+}
+
+/// Contents of the screen of the arcade cabinet.
+#[derive(Default)]
+struct Screen {
+    tiles: SparseGrid<i64>,
+    score: i64,
+    ball: Option<Point>,
+    paddle: Option<Point>,
+}
+
+impl Screen {
+    fn draw(&mut self, position: Point, tile_id: i64) {
+        if position == SCORE_POSITION {
+            self.score = tile_id;
+            return;
+        }
+        match tile_id {
+            TILE_BALL => self.ball = Some(position),
+            TILE_PADDLE => self.paddle = Some(position),
+            _ => (),
+        }
+        self.tiles.insert(position, tile_id);
+    }
+}
+
+/// Arcade cabinet running the game, keeping the screen and the score up to date with its output.
+pub struct Arcade {
+    cabinet: IntcodeHardware,
+    screen: Screen,
+    joystick: Option<Joystick>,
+}
+
+impl Arcade {
+    pub fn new(program: Program) -> Arcade {
+        Arcade { cabinet: IntcodeHardware::new(program), screen: Screen::default(), joystick: None }
+    }
+
+    /// Sets the number of quarters before the game starts, 2 lets the game be played for free.
+    pub fn insert_quarters(&mut self, quarters: i64) -> Result<(), AocError> {
+        Ok(self.cabinet.write_memory(0, quarters)?)
+    }
+
+    /// Runs the game until it needs the joystick or ends.
+    pub fn run(&mut self) -> Result<ArcadeState, AocError> {
+        let (cabinet, screen, joystick) = (&mut self.cabinet, &mut self.screen, &mut self.joystick);
+        let mut display = FramedOutput::new(|[x_pos, y_pos, tile_id]: [i64; 3]| {
+            screen.draw(tile_position(x_pos, y_pos)?, tile_id);
+            Ok(())
+        });
+        let state = cabinet.run_with_devices(&mut FnInput(|| joystick.take().map(Joystick::value)), &mut display)?;
+        display.finish()?;
+
+        match state {
+            RunState::Halted => Ok(ArcadeState::GameOver),
+            _ => Ok(ArcadeState::WaitingForJoystick),
+        }
+    }
+
+    /// Sets the joystick position for the next input the game reads.
+    pub fn move_joystick(&mut self, joystick: Joystick) {
+        self.joystick = Some(joystick);
+    }
+
+    pub fn score(&self) -> i64 {
+        self.screen.score
+    }
+
+    pub fn blocks_left(&self) -> usize {
+        self.screen.tiles.values().filter(|&&tile| tile == TILE_BLOCK).count()
+    }
 
-    let (output_sender, output_receiver) = mpsc::channel();
+    /// Joystick position which keeps the paddle under the ball.
+    pub fn autopilot(&self) -> Joystick {
+        match (self.screen.ball, self.screen.paddle) {
+            (Some(ball), Some(paddle)) if ball.x < paddle.x => Joystick::Left,
+            (Some(ball), Some(paddle)) if ball.x > paddle.x => Joystick::Right,
+            _ => Joystick::Neutral,
+        }
+    }
 
-    cabinet.start(program, None, vec![output_sender])?;
+    /// The screen followed by a line with the score.
+    pub fn render(&self) -> String {
+        let screen = self.screen.tiles.render(|tile| match tile.copied().unwrap_or(TILE_EMPTY) {
+            TILE_WALL => '#',
+            TILE_BLOCK => '=',
+            TILE_PADDLE => '_',
+            TILE_BALL => 'o',
+            _ => ' ',
+        });
+        format!("{}Score: {}\n", screen, self.screen.score)
+    }
+}
 
+/// Lets a human play the game in a terminal. The keys are read from the `keys`: the left and right arrows (or `a` and
+/// `d`) move the joystick, `q` quits and any other key keeps it in the neutral position. The screen is redrawn to the
+/// `terminal` after every move, with `\r\n` line endings so that it works in the raw mode. Returns the final score.
+pub fn play(program: Program, mut keys: impl Read, mut terminal: impl Write) -> Result<i64, AocError> {
+    let mut arcade = Arcade::new(program);
+    arcade.insert_quarters(FREE_PLAY)?;
 
     loop {
-        let x_pos = match output_receiver.recv() {
-            Ok(x_pos) => x_pos,
-            Err(_) => break
-        };
-        let y_pos = match output_receiver.recv() {
-            Ok(y_pos) => y_pos,
-            Err(err) => return Err(AocError::new(format!("Error reading y coordinate: {}", err)))
-        };
-        let tile_id = match output_receiver.recv() {
-            Ok(tile_id) => tile_id,
-            Err(err) => return Err(AocError::new(format!("Error reading tile id: {}", err)))
+        let state = arcade.run()?;
+        write!(terminal, "\x1b[2J\x1b[H{}", arcade.render().replace('\n', "\r\n"))?;
+        if state == ArcadeState::GameOver {
+            write!(terminal, "Game over\r\n")?;
+            terminal.flush()?;
+            return Ok(arcade.score());
+        }
+        terminal.flush()?;
+
+        match read_key(&mut keys)? {
+            Some(joystick) => arcade.move_joystick(joystick),
+            None => return Ok(arcade.score()),
+        }
+    }
+}
+
+/// Reads the next key press, `None` when the player quits or the keys run out.
+fn read_key(keys: &mut impl Read) -> Result<Option<Joystick>, AocError> {
+    let mut next_byte = || -> Result<Option<u8>, AocError> {
+        let mut byte = [0];
+        Ok(match keys.read(&mut byte)? {
+            0 => None,
+            _ => Some(byte[0]),
+        })
+    };
+
+    loop {
+        let joystick = match next_byte()? {
+            None | Some(b'q') => return Ok(None),
+            // Enter is ignored, so that the keys also work when the terminal is not in the raw mode.
+            Some(b'\r') | Some(b'\n') => continue,
+            Some(b'a') => Joystick::Left,
+            Some(b'd') => Joystick::Right,
+            // Arrow keys are sent as the escape sequences `ESC [ D` and `ESC [ C`.
+            Some(0x1b) => match (next_byte()?, next_byte()?) {
+                (Some(b'['), Some(b'D')) => Joystick::Left,
+                (Some(b'['), Some(b'C')) => Joystick::Right,
+                _ => Joystick::Neutral,
+            },
+            Some(_) => Joystick::Neutral,
         };
-        tiles.insert((x_pos, y_pos), tile_id);
+        return Ok(Some(joystick));
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Program;
+    type Parameters = ();
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Care Package"
+    }
+
+    fn parse(&self, input: &str) -> Result<Program, AocError> {
+        utils::parse_intcode_program(input)
     }
 
-    Ok(tiles.values().filter(|&&tile| {tile == TILE_BLOCK}).into_iter().count().to_string())
-}
\ No newline at end of file
+    fn part_one(&self, program: &Program, _parameters: &()) -> AocResult {
+        let mut arcade = Arcade::new(program.clone());
+        match arcade.run()? {
+            ArcadeState::GameOver => Ok(arcade.blocks_left().to_string()),
+            ArcadeState::WaitingForJoystick => Err(AocError::new(String::from("The game started without quarters"))),
+        }
+    }
+
+    fn part_two(&self, program: &Program, _parameters: &()) -> AocResult {
+        let mut arcade = Arcade::new(program.clone());
+        arcade.insert_quarters(FREE_PLAY)?;
+        while arcade.run()? == ArcadeState::WaitingForJoystick {
+            arcade.move_joystick(arcade.autopilot());
+        }
+
+        match arcade.blocks_left() {
+            0 => Ok(arcade.score().to_string()),
+            blocks => Err(AocError::new(format!("The autopilot lost the game with {} blocks left", blocks))),
+        }
+    }
+}
+
+fn tile_position(x_pos: i64, y_pos: i64) -> Result<Point, IntcodeComputerError> {
+    Point::try_from_coordinates(x_pos, y_pos)
+        .ok_or_else(|| IntcodeComputerError::new(format!("Tile position ({}, {}) out of the screen", x_pos, y_pos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::intcode_computer::assembler;
+
+    #[test]
+    fn test_arcade() {
+        // Draws the paddle, the ball and a block, and waits for the joystick. Moving it left breaks the block and
+        // scores 100 points, any other move ends the game right away.
+        let source = "
+                    out  #2
+                    out  #2
+                    out  #3
+                    out  #0
+                    out  #1
+                    out  #4
+                    out  #1
+                    out  #0
+                    out  #2
+                    inp  [joystick]
+                    eqs  [joystick], #-1, [flag]
+                    jif  [flag], #end
+                    out  #1
+                    out  #0
+                    out  #0
+                    out  #-1
+                    out  #0
+                    out  #100
+            end:    hlt
+            joystick: db 0
+            flag:   db   0";
+        let program = assembler::assemble(source).unwrap();
+
+        let mut arcade = Arcade::new(program.clone());
+        assert_eq!(arcade.run().unwrap(), ArcadeState::WaitingForJoystick);
+        assert_eq!(arcade.blocks_left(), 1);
+        assert_eq!(arcade.render(), " = \no  \n  _\nScore: 0\n");
+        assert_eq!(arcade.autopilot(), Joystick::Left);
+        arcade.move_joystick(arcade.autopilot());
+        assert_eq!(arcade.run().unwrap(), ArcadeState::GameOver);
+        assert_eq!(arcade.blocks_left(), 0);
+        assert_eq!(arcade.score(), 100);
+
+        let mut arcade = Arcade::new(program);
+        arcade.run().unwrap();
+        arcade.move_joystick(Joystick::Right);
+        assert_eq!(arcade.run().unwrap(), ArcadeState::GameOver);
+        assert_eq!((arcade.blocks_left(), arcade.score()), (1, 0));
+    }
+
+    #[test]
+    fn test_read_key() {
+        let mut keys: &[u8] = b"\x1b[Dd\n \x1b[Cq";
+        let mut pressed = vec![];
+        while let Some(joystick) = read_key(&mut keys).unwrap() {
+            pressed.push(joystick);
+        }
+        assert_eq!(pressed, vec![Joystick::Left, Joystick::Right, Joystick::Neutral, Joystick::Right]);
+    }
+}
@@ -1,106 +1,224 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use crate::aoc_error::{AocError, AocResult};
+use crate::solution::{self, Parameters, Solution};
+use crate::utils::graph::Graph;
+use crate::utils::input_conversion;
+
+const ORE: &str = "ORE";
+const FUEL: &str = "FUEL";
 
 #[derive(Clone)]
-struct Component {
+pub struct Component {
     name: String,
-    amount: u32,
+    amount: u64,
 }
 
 impl Component {
-    fn new(name: String, amount: u32) -> Self { Self { name, amount } }
+    pub fn new(name: String, amount: u64) -> Self { Self { name, amount } }
 }
 
 #[derive(Clone)]
-struct Reaction {
+pub struct Reaction {
     inputs: Vec<Component>,
     output: Component,
 }
 
 impl Reaction {
-    fn new(inputs: Vec<Component>, output: Component) -> Self { Self { inputs, output } }
+    pub fn new(inputs: Vec<Component>, output: Component) -> Self { Self { inputs, output } }
+}
+
+/// Reactions by the name of their output, together with an order of the chemicals in which every chemical comes
+/// before the inputs of its reaction.
+pub struct Reactions {
+    reactions: HashMap<String, Reaction>,
+    order: Vec<String>,
 }
 
+impl Reactions {
+    /// Parses reactions written as `7 A, 1 B => 1 C`, one per line.
+    pub fn parse(input: &str) -> Result<Reactions, AocError> {
+        Reactions::new(parse_recipe(input)?)
+    }
+
+    pub fn new(reactions: Vec<Reaction>) -> Result<Reactions, AocError> {
+        let mut reaction_map: HashMap<String, Reaction> = HashMap::new();
+        let mut dependencies = Graph::new();
+        for reaction in reactions {
+            for input in &reaction.inputs {
+                dependencies.add_edge(reaction.output.name.clone(), input.name.clone());
+            }
+            if let Some(duplicate_record) = reaction_map.insert(reaction.output.name.clone(), reaction) {
+                return Err(AocError::parse(format!("Two or more reaction outputing {}", duplicate_record.output.name)));
+            }
+        }
 
-pub fn first_star(input: &str) -> AocResult {
-    let recipe = parse_recipe(input)?;
-    let mut recipe_map: HashMap<String, Reaction> = HashMap::new();
-    for reaction in recipe {
-        if let Some(duplicate_record) = recipe_map.insert(reaction.output.name.clone(), reaction) {
-            return Err(AocError::new(format!("Two or more reaction outputing {}", duplicate_record.output.name)));
+        let order = dependencies.topological_sort()
+            .ok_or_else(|| AocError::parse(String::from("The reactions depend on each other in a cycle")))?;
+        if let Some(name) = order.iter().find(|name| *name != ORE && !reaction_map.contains_key(*name)) {
+            return Err(AocError::parse(format!("No reaction outputs {}", name)));
         }
+        Ok(Reactions { reactions: reaction_map, order })
     }
 
-    let mut reaction_queue: VecDeque<Component> = VecDeque::new();
-    let mut extra_resources: HashMap<String, u32> = HashMap::new();
-    reaction_queue.push_front(Component::new("FUEL".to_string(), 1));
-
-    let mut total_ore = 0;
-    while let Some(mut component) = reaction_queue.pop_back() {
-        let extra_resource_record = extra_resources.entry(component.name.clone()).or_insert(0);
-        let used_extra = std::cmp::min(*extra_resource_record, component.amount);
-        *extra_resource_record -= used_extra;
-        component.amount -= used_extra;
-        
-        let (mut inputs, extra_amount) = get_inputs_for(&component, &recipe_map)?;
-        // println!("popped: {} {} ({})", component.name, component.amount, extra_amount);
-        extra_resources.insert(component.name, extra_amount);
-        println!("{:?}", extra_resources);
-        for input in &mut inputs {
-            if input.amount > 0 {
-                if input.name == "ORE" {
-                    total_ore += input.amount;
-                    // println!("ORE {}", total_ore);
-                } else {
-                    // println!("pushed: {} {}", input.name, input.amount);
-                    reaction_queue.push_front(Component::new(input.name.clone(), input.amount));
-                }
+    /// Amount of ORE needed to produce the `amount` of the `chemical`, reusing the leftovers of the reactions.
+    pub fn ore_required(&self, chemical: &str, amount: u64) -> Result<u128, AocError> {
+        if chemical == ORE {
+            return Ok(amount as u128);
+        }
+        if !self.reactions.contains_key(chemical) {
+            return Err(AocError::new(format!("Can't produce {}", chemical)));
+        }
+
+        let mut needed: HashMap<&str, u128> = HashMap::new();
+        needed.insert(chemical, amount as u128);
+        // All the chemicals which need the current one have been processed before it, so its amount is final.
+        for name in &self.order {
+            let amount = match needed.get(name.as_str()) {
+                Some(amount) if name != ORE => *amount,
+                _ => continue,
+            };
+            let reaction = &self.reactions[name];
+            let runs = amount.div_ceil(reaction.output.amount as u128);
+            for input in &reaction.inputs {
+                *needed.entry(&input.name).or_insert(0) += runs * input.amount as u128;
             }
         }
+        Ok(needed.get(ORE).copied().unwrap_or(0))
+    }
+
+    /// Largest amount of the `chemical` which can be produced from the `ore`.
+    pub fn max_producible(&self, chemical: &str, ore: u128) -> Result<u64, AocError> {
+        if self.ore_required(chemical, 1)? > ore {
+            return Ok(0);
+        }
+
+        // Find an amount which can't be produced, then bisect between it and the largest one known to be producible.
+        let mut producible = 1;
+        let mut too_much = 2;
+        while self.ore_required(chemical, too_much)? <= ore {
+            producible = too_much;
+            too_much = too_much.checked_mul(2).ok_or_else(|| AocError::new(format!("Too much {} to count", chemical)))?;
+        }
+        while too_much - producible > 1 {
+            let middle = producible + (too_much - producible) / 2;
+            match self.ore_required(chemical, middle)? <= ore {
+                true => producible = middle,
+                false => too_much = middle,
+            }
+        }
+        Ok(producible)
+    }
+}
+
+pub struct Day14;
+
+pub struct Day14Parameters {
+    /// ORE available for the fuel of the second part.
+    pub ore: u64,
+}
+
+impl Default for Day14Parameters {
+    fn default() -> Self {
+        Day14Parameters { ore: 1_000_000_000_000 }
+    }
+}
+
+impl Parameters for Day14Parameters {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "ore" => self.ore = solution::parse_parameter(name, value)?,
+            _ => return Err(solution::unknown_parameter(name)),
+        }
+        Ok(())
     }
-    Ok(total_ore.to_string())
 }
 
-fn get_inputs_for(component: &Component, reactions: &HashMap<String, Reaction>) -> Result<(Vec<Component>, u32), AocError> {
-    let reaction = reactions.get(&component.name).ok_or_else(||AocError::new(format!("Can't produce {}", component.name)))?;
+impl Solution for Day14 {
+    type Input = Reactions;
+    type Parameters = Day14Parameters;
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Stoichiometry"
+    }
 
-    let mut num_reactions = 0;
-    while reaction.output.amount * num_reactions < component.amount {
-        num_reactions += 1;
+    fn parse(&self, input: &str) -> Result<Reactions, AocError> {
+        Reactions::parse(input)
     }
-    let amount_extra = reaction.output.amount * num_reactions - component.amount;
-    let mut inputs = reaction.inputs.clone();
-    for input in &mut inputs {
-        input.amount *= num_reactions;
+
+    fn part_one(&self, reactions: &Reactions, _parameters: &Day14Parameters) -> AocResult {
+        Ok(reactions.ore_required(FUEL, 1)?.to_string())
+    }
+
+    fn part_two(&self, reactions: &Reactions, parameters: &Day14Parameters) -> AocResult {
+        Ok(reactions.max_producible(FUEL, parameters.ore as u128)?.to_string())
     }
-    
-    Ok((inputs, amount_extra))
 }
 
 fn parse_recipe(input: &str) -> Result<Vec<Reaction>, AocError> {
     let mut reactions: Vec<Reaction> = vec![];
-    for line in input.lines() {
-        let (inputs, output) = line.split_once("=>").ok_or_else(||AocError::new(format!("Invalid input format")))?;
-        
-        // Parse inputs of the reaction (up to '=>').
-        let mut inputs = inputs.split_ascii_whitespace();
-        let mut input_components: Vec<Component> = vec![];
-        while let Some(Ok(amount)) = inputs.next().map(str::parse::<u32>) {
-            let name = inputs.next().ok_or_else(||AocError::new(format!("Invalid input format")))?
-                            .trim_end_matches(",");
-            input_components.push(Component::new(name.to_owned(), amount));
-        }   
-
-        // Parse output of the reaction.
-        let mut output = output.split_ascii_whitespace();
-        let output_amount = output.next().map(str::parse::<u32>)
-                                .map(Result::ok).flatten().ok_or_else(||AocError::new(format!("Invalid input format")))?;
-        let output_name = output.next().ok_or_else(||AocError::new(format!("Invalid input format")))?
-                                .trim_end_matches(",").to_owned();
-
-        let reaction = Reaction::new(input_components, Component::new(output_name, output_amount));
-        reactions.push(reaction);
+    for line in input_conversion::lines(input) {
+        let (inputs, output) = line.split_once(line.whole(), "=>")?;
+        let input_components = line.quantities::<u64>(inputs)?.into_iter()
+            .map(|(amount, name)| Component::new(name.to_owned(), amount))
+            .collect();
+
+        let output_component = match line.quantities::<u64>(output)?.as_slice() {
+            [(amount, _)] if *amount == 0 => return Err(line.error_at(output.trim(), String::from("Expected a positive amount"))),
+            [(amount, name)] => Component::new(name.to_string(), *amount),
+            _ => return Err(line.error_at(output.trim(), String::from("Expected a single output"))),
+        };
+        reactions.push(Reaction::new(input_components, output_component));
     }
 
     Ok(reactions)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REACTIONS: &str = "\
+10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+    #[test]
+    fn test_ore_required() {
+        let reactions = Reactions::parse(REACTIONS).unwrap();
+        assert_eq!(reactions.ore_required(FUEL, 1).unwrap(), 31);
+        assert_eq!(reactions.ore_required("C", 1).unwrap(), 11);
+        assert_eq!(reactions.ore_required("A", 11).unwrap(), 20);
+        assert_eq!(reactions.ore_required(ORE, 5).unwrap(), 5);
+        assert!(reactions.ore_required("X", 1).is_err());
+
+        let ore = |amount| Component::new(String::from(ORE), amount);
+        let reactions = Reactions::new(vec![
+            Reaction::new(vec![ore(2)], Component::new(String::from("A"), 1)),
+            Reaction::new(vec![ore(1), Component::new(String::from("A"), 2)], Component::new(String::from("B"), 3)),
+        ]).unwrap();
+        assert_eq!(reactions.ore_required("B", 4).unwrap(), 10);
+
+        let cycle = vec![
+            Reaction::new(vec![Component::new(String::from("B"), 1)], Component::new(String::from("A"), 1)),
+            Reaction::new(vec![Component::new(String::from("A"), 1)], Component::new(String::from("B"), 1)),
+        ];
+        assert!(Reactions::new(cycle).is_err());
+    }
+
+    #[test]
+    fn test_max_producible() {
+        let reactions = Reactions::parse(REACTIONS).unwrap();
+        assert_eq!(reactions.max_producible(FUEL, 30).unwrap(), 0);
+        assert_eq!(reactions.max_producible(FUEL, 61).unwrap(), 1);
+        assert_eq!(reactions.max_producible(FUEL, 62).unwrap(), 2);
+        assert_eq!(reactions.max_producible("A", 25).unwrap(), 20);
+        assert_eq!(reactions.max_producible("B", 10).unwrap(), 10);
+    }
+}
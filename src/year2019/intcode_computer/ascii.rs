@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use super::{IntcodeComputerError, IntcodeHardware, Program, Register, RegisterType, RunState};
+
+/// Intcode computer for programs which talk in ASCII text. Lines are sent as their codes followed by a new line and
+/// raw values can be mixed in. The outputs are kept as they were written, and can be taken either raw or split into
+/// the text and the values which are not ASCII codes, typically the final answer. The inputs and the outputs use the
+/// queue devices of the hardware.
+pub struct AsciiComputer<T = RegisterType> {
+    hardware: IntcodeHardware<T>,
+    inputs: VecDeque<T>,
+    outputs: Vec<T>,
+}
+
+impl<T: Register> AsciiComputer<T> {
+    pub fn new(program: Program<T>) -> Self {
+        AsciiComputer { hardware: IntcodeHardware::new(program), inputs: VecDeque::new(), outputs: vec![] }
+    }
+
+    pub fn hardware_mut(&mut self) -> &mut IntcodeHardware<T> {
+        &mut self.hardware
+    }
+
+    /// Queues the `line`, which must be ASCII, followed by a new line.
+    pub fn send_line(&mut self, line: &str) -> Result<(), IntcodeComputerError> {
+        if !line.is_ascii() {
+            return Err(IntcodeComputerError::new(format!("Input '{}' is not ASCII", line)));
+        }
+        // Every ASCII code fits into any register type.
+        self.inputs.extend(line.bytes().chain(Some(b'\n')).map(|code| T::from_u8(code).unwrap()));
+        Ok(())
+    }
+
+    /// Queues a raw value, for programs which mix numbers into the conversation.
+    pub fn send_value(&mut self, value: T) {
+        self.inputs.push_back(value);
+    }
+
+    /// Runs the program until it halts or waits for more input.
+    pub fn run(&mut self) -> Result<RunState<T>, IntcodeComputerError> {
+        self.hardware.run_with_devices(&mut self.inputs, &mut self.outputs)
+    }
+
+    /// Runs the program until it halts, failing if it runs out of the queued input.
+    pub fn run_to_halt(&mut self) -> Result<(), IntcodeComputerError> {
+        match self.run()? {
+            RunState::Halted => Ok(()),
+            _ => Err(IntcodeComputerError::OutOfInputs),
+        }
+    }
+
+    /// Values output since the last call, for programs which answer with numbers.
+    pub fn take_outputs(&mut self) -> Vec<T> {
+        mem::take(&mut self.outputs)
+    }
+
+    /// Text output since the last call, together with the values which are not ASCII codes.
+    pub fn take_text(&mut self) -> (String, Vec<T>) {
+        let mut text = String::new();
+        let mut values = vec![];
+        for value in self.take_outputs() {
+            match value.to_u8().filter(u8::is_ascii) {
+                Some(code) => text.push(char::from(code)),
+                None => values.push(value),
+            }
+        }
+        (text, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation() {
+        // Echoes the input, ends with a large value once it reads a 0.
+        let program = vec![3, 100, 1005, 100, 9, 104, 1000, 99, 0, 4, 100, 1105, 1, 0];
+        let mut computer = AsciiComputer::new(program);
+        computer.send_line("Hello").unwrap();
+        assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+        assert_eq!(computer.take_text(), (String::from("Hello\n"), vec![]));
+        assert!(computer.send_line("Ünïcode").is_err());
+
+        computer.send_value(0);
+        computer.run_to_halt().unwrap();
+        assert_eq!(computer.take_text(), (String::new(), vec![1000]));
+    }
+
+    #[test]
+    fn test_raw_outputs() {
+        // Echoes the input until it reads a 0.
+        let program = vec![3, 100, 1005, 100, 6, 99, 4, 100, 1105, 1, 0];
+        let mut computer = AsciiComputer::new(program);
+        computer.send_line("A").unwrap();
+        assert!(computer.run_to_halt().is_err());
+        computer.send_value(0);
+        computer.run_to_halt().unwrap();
+        assert_eq!(computer.take_outputs(), vec![65, 10]);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use super::{IntcodeComputerError, Program, Register};
+
+/// Mnemonic, opcode and number of parameters of every instruction.
+const INSTRUCTIONS: [(&str, i64, usize); 10] = [
+    ("add", 1, 3), ("mul", 2, 3), ("inp", 3, 1), ("out", 4, 1), ("jit", 5, 2),
+    ("jif", 6, 2), ("lst", 7, 3), ("eqs", 8, 3), ("rbo", 9, 1), ("hlt", 99, 0),
+];
+
+const POSITION_MODE: i64 = 0;
+const IMMEDIATE_MODE: i64 = 1;
+const RELATIVE_MODE: i64 = 2;
+
+enum Value<T> {
+    Number(T),
+    Label(String),
+}
+
+/// Assembles the `source` into an Intcode program. The syntax matches the output of the disassembler:
+///
+/// ```text
+/// ; Counts down from 5 and outputs every value.
+/// start:  out  [counter]
+///         add  [counter], #-1, [counter]
+///         jit  [counter], #start
+///         hlt
+/// counter: db  5
+/// ```
+///
+/// * Each line holds an instruction or a `db` directive with comma separated data values.
+/// * Parameters are `[value]` in the position mode, `#value` in the immediate mode and `rb+offset` or
+///   `rb-offset` in the relative mode. A value is a number or a label.
+/// * `label:` defines a label with the address of the following item, `; ` starts a comment. A numeric
+///   `address:` prefix, as produced by the disassembler, is ignored.
+pub fn assemble<T: Register>(source: &str) -> Result<Program<T>, IntcodeComputerError> {
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    let mut words: Vec<(usize, Value<T>)> = vec![];
+
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut line = line.split(';').next().unwrap_or("").trim();
+
+        while let Some((prefix, rest)) = line.split_once(':') {
+            let prefix = prefix.trim();
+            if is_label(prefix) {
+                if labels.insert(prefix.to_owned(), words.len()).is_some() {
+                    return Err(line_error(line_number, format!("Label '{}' defined more than once", prefix)));
+                }
+            } else if prefix.parse::<usize>().is_err() {
+                return Err(line_error(line_number, format!("Invalid label '{}'", prefix)));
+            }
+            line = rest.trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let (mnemonic, parameters) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let parameters = parameters.split(',').map(str::trim).filter(|parameter| !parameter.is_empty()).collect::<Vec<&str>>();
+
+        if mnemonic == "db" {
+            for parameter in parameters {
+                words.push((line_number, parse_value(parameter, line_number)?));
+            }
+            continue;
+        }
+
+        let (_, opcode, num_parameters) = INSTRUCTIONS.iter().find(|(name, _, _)| *name == mnemonic)
+            .ok_or_else(|| line_error(line_number, format!("Unknown mnemonic '{}'", mnemonic)))?;
+        if parameters.len() != *num_parameters {
+            return Err(line_error(line_number, format!(
+                "'{}' takes {} parameters, got {}", mnemonic, num_parameters, parameters.len())));
+        }
+
+        let mut opcode_with_modes = *opcode;
+        let mut values = vec![];
+        let mut mode_multiplier = 100;
+        for (parameter_index, parameter) in parameters.into_iter().enumerate() {
+            let (mode, value) = parse_parameter(parameter, line_number)?;
+            if mode == IMMEDIATE_MODE && writes_to_parameter(*opcode, parameter_index) {
+                return Err(line_error(line_number, String::from("Output parameter can't be in immediate mode")));
+            }
+            opcode_with_modes += mode * mode_multiplier;
+            mode_multiplier *= 10;
+            values.push((line_number, value));
+        }
+        let opcode_with_modes = T::from_i64(opcode_with_modes)
+            .ok_or_else(|| line_error(line_number, format!("Opcode {} does not fit the register", opcode_with_modes)))?;
+        words.push((line_number, Value::Number(opcode_with_modes)));
+        words.extend(values);
+    }
+
+    words.into_iter().map(|(line_number, value)| match value {
+        Value::Number(number) => Ok(number),
+        Value::Label(label) => labels.get(&label).and_then(|address| T::from_usize(*address))
+            .ok_or_else(|| line_error(line_number, format!("Undefined label '{}'", label))),
+    }).collect()
+}
+
+fn parse_parameter<T: Register>(parameter: &str, line_number: usize) -> Result<(i64, Value<T>), IntcodeComputerError> {
+    if let Some(value) = parameter.strip_prefix('#') {
+        Ok((IMMEDIATE_MODE, parse_value(value, line_number)?))
+    } else if let Some(value) = parameter.strip_prefix('[').and_then(|value| value.strip_suffix(']')) {
+        Ok((POSITION_MODE, parse_value(value, line_number)?))
+    } else if let Some(offset) = parameter.strip_prefix("rb") {
+        let offset = offset.trim();
+        let value = match offset.strip_prefix('+') {
+            Some(positive_offset) => parse_value(positive_offset, line_number)?,
+            None if offset.starts_with('-') => parse_value(offset, line_number)?,
+            None => return Err(line_error(line_number, format!("Invalid relative parameter '{}'", parameter))),
+        };
+        Ok((RELATIVE_MODE, value))
+    } else {
+        Err(line_error(line_number, format!("Parameter '{}' has no mode, use [x], #x or rb+x", parameter)))
+    }
+}
+
+fn parse_value<T: Register>(value: &str, line_number: usize) -> Result<Value<T>, IntcodeComputerError> {
+    let value = value.trim();
+    if is_label(value) {
+        return Ok(Value::Label(value.to_owned()));
+    }
+    value.parse::<T>().map(Value::Number)
+        .map_err(|_| line_error(line_number, format!("Invalid value '{}'", value)))
+}
+
+fn writes_to_parameter(opcode: i64, parameter_index: usize) -> bool {
+    match opcode {
+        1 | 2 | 7 | 8 => parameter_index == 2,
+        3 => parameter_index == 0,
+        _ => false,
+    }
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.is_ascii_alphabetic() || first == '_').unwrap_or(false)
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+fn line_error(line_number: usize, message: String) -> IntcodeComputerError {
+    IntcodeComputerError::new(format!("Assembler error on line {}: {}", line_number, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{disassembler, IntcodeHardware};
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            ; Counts down from 3 and outputs every value.
+            start:  out  [counter]
+                    add  [counter], #-1, [counter]
+                    jit  [counter], #start
+                    rbo  #counter
+                    out  rb+0
+                    hlt
+            counter: db  3";
+        let program = assemble::<i64>(source).unwrap();
+        assert_eq!(program, vec![4, 14, 1001, 14, -1, 14, 1005, 14, 0, 109, 14, 204, 0, 99, 3]);
+        assert_eq!(IntcodeHardware::new(program).run_with_inputs(&[]).unwrap(), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_assemble_disassembled_program() {
+        let program = vec![1001, 9, 10, 9, 204, -3, 109, 19, 99, 0, 77, 3];
+        assert_eq!(assemble::<i64>(&disassembler::disassemble(&program)).unwrap(), program);
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        assert!(assemble::<i64>("add [1], #2").is_err());
+        assert!(assemble::<i64>("add [1], #2, #3").is_err());
+        assert!(assemble::<i64>("out 5").is_err());
+        assert!(assemble::<i64>("jmp #5").is_err());
+        assert!(assemble::<i64>("out [nowhere]").is_err());
+        assert!(assemble::<i64>("a: hlt\na: hlt").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+use super::{disassembler, IntcodeComputerError, IntcodeHardware, Program, Register, RegisterType, RunState};
+
+const HELP: &str = "\
+Commands:
+    s, step [N]              Execute N instructions (default: 1)
+    c, continue              Run until a breakpoint, watchpoint, missing input or halt
+    b, break <ADDRESS>       Set a breakpoint on the instruction at the address
+    w, watch <ADDRESS>       Stop whenever the value at the address changes
+    d, delete <ADDRESS>      Remove breakpoints and watchpoints at the address
+    i, input <VALUE>...      Queue input values
+    r, registers             Print the instruction pointer and the relative base
+    x, memory <ADDRESS> [N]  Print N memory cells starting at the address (default: 1, at most 256)
+    set <ADDRESS> <VALUE>    Write the value to the memory
+    l, list [ADDRESS] [N]    Disassemble N instructions from the address (default: ip, 5, at most 256)
+    info                     List breakpoints and watchpoints
+    h, help                  Print this message
+    q, quit                  Exit the debugger";
+
+/// Most memory cells or instructions the `memory` and `list` commands print at once.
+const MAX_PRINTED_LINES: usize = 256;
+
+/// Reason why the debugger stopped executing the program.
+#[derive(Debug, PartialEq)]
+pub enum StopReason<T = RegisterType> {
+    /// A single step finished without anything else happening.
+    Stepped,
+    Breakpoint(usize),
+    Watchpoint { address: usize, old_value: T, new_value: T },
+    NeedsInput,
+    Halted,
+}
+
+pub enum CommandResult {
+    /// Text to be shown to the user.
+    Message(String),
+    Quit,
+}
+
+/// Debugger driving the `IntcodeHardware` one instruction at a time. Outputs of the program are collected
+/// and can be taken with `take_outputs`.
+pub struct Debugger<T = RegisterType> {
+    hardware: IntcodeHardware<T>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    outputs: Vec<T>,
+}
+
+impl<T: Register> Debugger<T> {
+    pub fn new(program: Program<T>) -> Self {
+        Debugger { hardware: IntcodeHardware::new(program), breakpoints: BTreeSet::new(),
+                   watchpoints: BTreeSet::new(), outputs: vec![] }
+    }
+
+    pub fn hardware(&self) -> &IntcodeHardware<T> {
+        &self.hardware
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    /// Removes breakpoints and watchpoints at the `address`. Returns whether there were any.
+    pub fn remove_points(&mut self, address: usize) -> bool {
+        let removed_breakpoint = self.breakpoints.remove(&address);
+        let removed_watchpoint = self.watchpoints.remove(&address);
+        removed_breakpoint || removed_watchpoint
+    }
+
+    pub fn push_input(&mut self, value: T) {
+        self.hardware.push_input(value);
+    }
+
+    pub fn take_outputs(&mut self) -> Vec<T> {
+        std::mem::take(&mut self.outputs)
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Result<StopReason<T>, IntcodeComputerError> {
+        let watched_values = self.watchpoints.iter()
+            .map(|address| (*address, self.hardware.read_memory(*address)))
+            .collect::<Vec<(usize, T)>>();
+
+        match self.hardware.step()? {
+            Some(RunState::Output(value)) => self.outputs.push(value),
+            Some(RunState::NeedsInput) => return Ok(StopReason::NeedsInput),
+            Some(RunState::Halted) => return Ok(StopReason::Halted),
+            None => (),
+        }
+
+        for (address, old_value) in watched_values {
+            let new_value = self.hardware.read_memory(address);
+            if new_value != old_value {
+                return Ok(StopReason::Watchpoint { address, old_value, new_value });
+            }
+        }
+        Ok(StopReason::Stepped)
+    }
+
+    /// Runs the program until it hits a breakpoint or a watchpoint, needs an input or halts. At least one
+    /// instruction is executed, so that continuing from a breakpoint moves past it.
+    pub fn resume(&mut self) -> Result<StopReason<T>, IntcodeComputerError> {
+        loop {
+            let reason = self.step()?;
+            if reason != StopReason::Stepped {
+                return Ok(reason);
+            }
+            if self.breakpoints.contains(&self.hardware.ip()) {
+                return Ok(StopReason::Breakpoint(self.hardware.ip()));
+            }
+        }
+    }
+
+    /// Executes a single debugger command, see `HELP` for the list of commands.
+    pub fn execute_command(&mut self, command: &str) -> Result<CommandResult, IntcodeComputerError> {
+        let mut words = command.split_whitespace();
+        let name = match words.next() {
+            Some(name) => name,
+            None => return Ok(CommandResult::Message(String::new())),
+        };
+        let arguments = words.map(parse_number).collect::<Result<Vec<T>, IntcodeComputerError>>()?;
+        let argument = |index: usize| arguments.get(index).cloned()
+            .ok_or_else(|| IntcodeComputerError::new(format!("Missing argument for '{}'", name)));
+
+        let message = match name {
+            "s" | "step" => {
+                let num_steps = arguments.first().map(to_address).transpose()?.unwrap_or(1);
+                let mut reason = StopReason::Stepped;
+                for _ in 0..num_steps {
+                    reason = self.step()?;
+                    if reason != StopReason::Stepped {
+                        break;
+                    }
+                }
+                self.describe_stop(&reason)
+            },
+            "c" | "continue" => {
+                let reason = self.resume()?;
+                self.describe_stop(&reason)
+            },
+            "b" | "break" => {
+                self.add_breakpoint(to_address(&argument(0)?)?);
+                format!("Breakpoint set at {}", argument(0)?)
+            },
+            "w" | "watch" => {
+                self.add_watchpoint(to_address(&argument(0)?)?);
+                format!("Watchpoint set at {}", argument(0)?)
+            },
+            "d" | "delete" => match self.remove_points(to_address(&argument(0)?)?) {
+                true => format!("Removed points at {}", argument(0)?),
+                false => format!("No points at {}", argument(0)?),
+            },
+            "i" | "input" => {
+                arguments.iter().for_each(|value| self.push_input(value.clone()));
+                format!("Queued {} input value(s)", arguments.len())
+            },
+            "r" | "registers" => format!("ip: {}, relative base: {}", self.hardware.ip(), self.hardware.relative_base()),
+            "x" | "memory" => {
+                let start = to_address(&argument(0)?)?;
+                let count = arguments.get(1).map(to_address).transpose()?.unwrap_or(1).min(MAX_PRINTED_LINES);
+                let end = start.checked_add(count)
+                    .ok_or_else(|| IntcodeComputerError::new(format!("Memory range {}+{} out of bounds", start, count)))?;
+                (start..end).map(|address| format!("{:>5}: {}", address, self.hardware.read_memory(address)))
+                    .collect::<Vec<String>>().join("\n")
+            },
+            "set" => {
+                self.hardware.write_memory(to_address(&argument(0)?)?, argument(1)?)?;
+                format!("[{}] = {}", argument(0)?, argument(1)?)
+            },
+            "l" | "list" => {
+                let start = match arguments.first() {
+                    Some(address) => to_address(address)?,
+                    None => self.hardware.ip(),
+                };
+                let count = arguments.get(1).map(to_address).transpose()?.unwrap_or(5).min(MAX_PRINTED_LINES);
+                self.list_instructions(start, count)
+            },
+            "info" => format!("Breakpoints: {:?}\nWatchpoints: {:?}", self.breakpoints, self.watchpoints),
+            "h" | "help" => HELP.to_owned(),
+            "q" | "quit" => return Ok(CommandResult::Quit),
+            unknown => return Err(IntcodeComputerError::new(format!("Unknown command '{}', try 'help'", unknown))),
+        };
+        Ok(CommandResult::Message(message))
+    }
+
+    /// Reads commands from the `input` until it's exhausted or the user quits.
+    pub fn run_repl(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "{}", self.list_instructions(self.hardware.ip(), 1))?;
+        let mut lines = input.lines();
+        loop {
+            write!(output, "(debug) ")?;
+            output.flush()?;
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            match self.execute_command(&line) {
+                Ok(CommandResult::Message(message)) if message.is_empty() => (),
+                Ok(CommandResult::Message(message)) => writeln!(output, "{}", message)?,
+                Ok(CommandResult::Quit) => return Ok(()),
+                Err(error) => writeln!(output, "{}", error)?,
+            }
+        }
+    }
+
+    fn describe_stop(&mut self, reason: &StopReason<T>) -> String {
+        let mut lines = self.take_outputs().iter().map(|value| format!("Output: {}", value)).collect::<Vec<String>>();
+        match reason {
+            StopReason::Stepped => (),
+            StopReason::Breakpoint(address) => lines.push(format!("Breakpoint at {}", address)),
+            StopReason::Watchpoint { address, old_value, new_value } =>
+                lines.push(format!("Watchpoint [{}]: {} -> {}", address, old_value, new_value)),
+            StopReason::NeedsInput => lines.push(String::from("Program needs input, queue it with 'input'")),
+            StopReason::Halted => lines.push(String::from("Program halted")),
+        }
+        lines.push(self.list_instructions(self.hardware.ip(), 1));
+        lines.join("\n")
+    }
+
+    fn list_instructions(&self, start: usize, count: usize) -> String {
+        let mut lines = vec![];
+        let mut address = start;
+        for _ in 0..count {
+            let marker = if address == self.hardware.ip() { "=>" } else { "  " };
+            let size = match self.hardware.instruction_at(address) {
+                Ok(instruction) => {
+                    lines.push(format!("{} {:>5}: {}", marker, address, disassembler::format_instruction(&instruction)));
+                    instruction.size()
+                },
+                Err(_) => {
+                    lines.push(format!("{} {:>5}: db   {}", marker, address, self.hardware.read_memory(address)));
+                    1
+                },
+            };
+            // The listing ends with the address space.
+            address = match address.checked_add(size) {
+                Some(next_address) => next_address,
+                None => break,
+            };
+        }
+        lines.join("\n")
+    }
+}
+
+fn parse_number<T: Register>(value: &str) -> Result<T, IntcodeComputerError> {
+    value.parse::<T>().map_err(|_| IntcodeComputerError::new(format!("Invalid number '{}'", value)))
+}
+
+fn to_address<T: Register>(value: &T) -> Result<usize, IntcodeComputerError> {
+    value.to_usize().ok_or_else(|| IntcodeComputerError::new(format!("Invalid address {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::assembler;
+
+    fn countdown_debugger() -> Debugger {
+        let program = assembler::assemble("
+            start:  out  [counter]
+                    add  [counter], #-1, [counter]
+                    jit  [counter], #start
+                    inp  [counter]
+                    hlt
+            counter: db  2").unwrap();
+        Debugger::new(program)
+    }
+
+    #[test]
+    fn test_breakpoints_and_watchpoints() {
+        let mut debugger = countdown_debugger();
+        debugger.add_breakpoint(6);
+        assert_eq!(debugger.resume().unwrap(), StopReason::Breakpoint(6));
+        assert_eq!(debugger.take_outputs(), vec![2]);
+        assert_eq!(debugger.hardware().read_memory(12), 1);
+
+        debugger.add_watchpoint(12);
+        assert_eq!(debugger.resume().unwrap(), StopReason::Watchpoint { address: 12, old_value: 1, new_value: 0 });
+        assert_eq!(debugger.take_outputs(), vec![1]);
+
+        assert!(debugger.remove_points(12));
+        assert!(!debugger.remove_points(12));
+        assert_eq!(debugger.resume().unwrap(), StopReason::NeedsInput);
+        debugger.push_input(7);
+        assert_eq!(debugger.resume().unwrap(), StopReason::Halted);
+        assert_eq!(debugger.hardware().read_memory(12), 7);
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut debugger = countdown_debugger();
+        let commands = "break 9\ncontinue\nregisters\nmemory 11 2\nquit\nstep\n";
+        let mut output = vec![];
+        debugger.run_repl(commands.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Output: 2\nOutput: 1\n"));
+        assert!(output.contains("=>     9: inp  [12]"));
+        assert!(output.contains("ip: 9, relative base: 0"));
+        assert!(output.contains("   11: 99\n   12: 0"));
+        assert_eq!(debugger.hardware().ip(), 9);
+
+        // Addresses up to the largest usize need a wider register type.
+        let mut debugger = Debugger::<i128>::new(vec![99]);
+        let output = debugger.execute_command("memory 0 18446744073709551615");
+        assert!(matches!(output, Ok(CommandResult::Message(message)) if message.lines().count() == MAX_PRINTED_LINES));
+        assert!(debugger.execute_command("memory 18446744073709551615 2").is_err());
+        let output = debugger.execute_command("list 0 1000000000");
+        assert!(matches!(output, Ok(CommandResult::Message(message)) if message.lines().count() == MAX_PRINTED_LINES));
+        let output = debugger.execute_command("list 18446744073709551614 3");
+        assert!(matches!(output, Ok(CommandResult::Message(message)) if message.lines().count() == 2));
+    }
+}
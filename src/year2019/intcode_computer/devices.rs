@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::sync::mpsc;
+
+use super::{IntcodeComputerError, RegisterType};
+
+/// Source of the values read by the input instructions, see `IntcodeHardware::run_with_devices`.
+pub trait InputDevice<T = RegisterType> {
+    /// Next input value, `None` when there is no input available at the moment.
+    fn read(&mut self) -> Result<Option<T>, IntcodeComputerError>;
+}
+
+/// Sink for the values written by the output instructions, see `IntcodeHardware::run_with_devices`.
+pub trait OutputDevice<T = RegisterType> {
+    fn write(&mut self, value: T) -> Result<(), IntcodeComputerError>;
+}
+
+/// Fixed queue of inputs.
+impl<T> InputDevice<T> for VecDeque<T> {
+    fn read(&mut self) -> Result<Option<T>, IntcodeComputerError> {
+        Ok(self.pop_front())
+    }
+}
+
+/// Collects all the outputs.
+impl<T> OutputDevice<T> for Vec<T> {
+    fn write(&mut self, value: T) -> Result<(), IntcodeComputerError> {
+        self.push(value);
+        Ok(())
+    }
+}
+
+/// Input read from a channel, waiting until a value arrives. Fails once all the senders are gone.
+pub struct ChannelInput<T = RegisterType> {
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> ChannelInput<T> {
+    pub fn new(receiver: mpsc::Receiver<T>) -> Self {
+        ChannelInput { receiver }
+    }
+}
+
+impl<T> InputDevice<T> for ChannelInput<T> {
+    fn read(&mut self) -> Result<Option<T>, IntcodeComputerError> {
+        self.receiver.recv().map(Some)
+            .map_err(|mpsc_error| IntcodeComputerError::new(format!("Could not read from the channel: {}", mpsc_error)))
+    }
+}
+
+/// Output sent to every one of the channels.
+pub struct ChannelOutput<T = RegisterType> {
+    senders: Vec<mpsc::Sender<T>>,
+}
+
+impl<T> ChannelOutput<T> {
+    pub fn new(senders: Vec<mpsc::Sender<T>>) -> Self {
+        ChannelOutput { senders }
+    }
+}
+
+impl<T: Clone> OutputDevice<T> for ChannelOutput<T> {
+    fn write(&mut self, value: T) -> Result<(), IntcodeComputerError> {
+        for sender in &self.senders {
+            sender.send(value.clone())
+                .map_err(|mpsc_error| IntcodeComputerError::new(format!("Could not send a value to the channel: {}", mpsc_error)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Input produced by a closure, e.g. a sensor reading the state of a simulation.
+pub struct FnInput<F>(pub F);
+
+impl<T, F: FnMut() -> Option<T>> InputDevice<T> for FnInput<F> {
+    fn read(&mut self) -> Result<Option<T>, IntcodeComputerError> {
+        Ok((self.0)())
+    }
+}
+
+/// Output handled by a closure.
+pub struct FnOutput<F>(pub F);
+
+impl<T, F: FnMut(T) -> Result<(), IntcodeComputerError>> OutputDevice<T> for FnOutput<F> {
+    fn write(&mut self, value: T) -> Result<(), IntcodeComputerError> {
+        (self.0)(value)
+    }
+}
+
+/// Groups the outputs into frames of `N` values, such as pairs of instructions or `(x, y, value)` triples, and hands
+/// every complete frame to the closure.
+pub struct FramedOutput<T, F, const N: usize> {
+    frame: Vec<T>,
+    handler: F,
+}
+
+impl<T, F: FnMut([T; N]) -> Result<(), IntcodeComputerError>, const N: usize> FramedOutput<T, F, N> {
+    pub fn new(handler: F) -> Self {
+        FramedOutput { frame: Vec::with_capacity(N), handler }
+    }
+
+    /// Fails if the program stopped in the middle of a frame.
+    pub fn finish(&self) -> Result<(), IntcodeComputerError> {
+        match self.frame.is_empty() {
+            true => Ok(()),
+            false => Err(IntcodeComputerError::new(format!("Incomplete output frame of {} values, expected {}",
+                                                           self.frame.len(), N))),
+        }
+    }
+}
+
+impl<T, F: FnMut([T; N]) -> Result<(), IntcodeComputerError>, const N: usize> OutputDevice<T> for FramedOutput<T, F, N> {
+    fn write(&mut self, value: T) -> Result<(), IntcodeComputerError> {
+        self.frame.push(value);
+        if self.frame.len() < N {
+            return Ok(());
+        }
+        let frame = std::mem::replace(&mut self.frame, Vec::with_capacity(N));
+        // The frame is full, so the conversion can't fail.
+        (self.handler)(<[T; N]>::try_from(frame).ok().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::IntcodeHardware;
+    use super::super::RunState;
+
+    #[test]
+    fn test_devices() {
+        // Echoes inputs until it reads a 0.
+        let program = vec![3, 100, 1005, 100, 6, 99, 4, 100, 1105, 1, 0];
+
+        let mut computer = IntcodeHardware::new(program.clone());
+        let mut output = vec![];
+        assert_eq!(computer.run_with_devices(&mut VecDeque::from(vec![7]), &mut output).unwrap(), RunState::NeedsInput);
+        assert_eq!(computer.run_with_devices(&mut VecDeque::from(vec![1000, 0]), &mut output).unwrap(), RunState::Halted);
+        assert_eq!(output, vec![7, 1000]);
+
+        let mut pairs = vec![];
+        let mut computer = IntcodeHardware::new(program.clone());
+        let mut output = FramedOutput::new(|[a, b]| { pairs.push((a, b)); Ok(()) });
+        computer.run_with_devices(&mut VecDeque::from(vec![1, 2, 3, 0]), &mut output).unwrap();
+        assert!(output.finish().is_err());
+        assert_eq!(pairs, vec![(1, 2)]);
+
+        let mut countdown = 3;
+        let mut total = 0;
+        let mut computer = IntcodeHardware::new(program);
+        computer.run_with_devices(&mut FnInput(|| { countdown -= 1; Some(countdown) }),
+                                  &mut FnOutput(|value| { total += value; Ok(()) })).unwrap();
+        assert_eq!(total, 3);
+    }
+}
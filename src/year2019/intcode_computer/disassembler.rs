@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use super::{decode_instruction, Instruction, ParameterMode, Register};
+
+/// Disassembles the `program` into a listing with one instruction per line:
+///
+/// ```text
+///     0: add  [9], #10, [9]           ; 1001,9,10,9
+///     4: out  rb+3                    ; 204,3
+///     6: hlt                          ; 99
+///     7: db   0, 77
+/// ```
+///
+/// Positional parameters are shown as `[address]`, immediate ones as `#value` and relative ones as `rb+offset`.
+/// Memory that does not decode into an instruction is listed as data (`db`). The program is decoded linearly,
+/// so data which happens to decode is listed as instructions.
+pub fn disassemble<T: Register>(program: &[T]) -> String {
+    let mut listing = String::new();
+    let mut data_start = None;
+    let mut address = 0;
+
+    while address < program.len() {
+        match decode_instruction(&program[address..], address) {
+            Ok(instruction) => {
+                if let Some(start) = data_start.take() {
+                    write_data(&mut listing, start, &program[start..address]);
+                }
+                let size = instruction.size();
+                let raw_instruction = join(&program[address..address + size], ",");
+                writeln!(listing, "{:>5}: {:<28} ; {}", address, format_instruction(&instruction), raw_instruction).unwrap();
+                address += size;
+            },
+            Err(_) => {
+                data_start.get_or_insert(address);
+                address += 1;
+            },
+        }
+    }
+    if let Some(start) = data_start {
+        write_data(&mut listing, start, &program[start..]);
+    }
+    listing
+}
+
+/// Formats the instruction as `mnemonic parameter, parameter, ...`.
+pub(super) fn format_instruction<T: Register>(instruction: &Instruction<T>) -> String {
+    let parameters = instruction.parameters().into_iter().map(format_parameter).collect::<Vec<String>>();
+    match parameters.is_empty() {
+        true => instruction.mnemonic().to_owned(),
+        false => format!("{:<4} {}", instruction.mnemonic(), parameters.join(", ")),
+    }
+}
+
+fn format_parameter<T: Register>(parameter: &ParameterMode<T>) -> String {
+    match parameter {
+        ParameterMode::Position(address) => format!("[{}]", address),
+        ParameterMode::Immediate(value) => format!("#{}", value),
+        ParameterMode::Relative(offset) if *offset < T::zero() => format!("rb{}", offset),
+        ParameterMode::Relative(offset) => format!("rb+{}", offset),
+    }
+}
+
+fn write_data<T: Register>(listing: &mut String, address: usize, data: &[T]) {
+    writeln!(listing, "{:>5}: {:<4} {}", address, "db", join(data, ", ")).unwrap();
+}
+
+fn join<T: Register>(values: &[T], separator: &str) -> String {
+    values.iter().map(T::to_string).collect::<Vec<String>>().join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let listing = disassemble::<i64>(&[1001, 9, 10, 9, 204, -3, 109, 19, 99, 0, 77, 3]);
+        let expected_listing = [
+            "    0: add  [9], #10, [9]           ; 1001,9,10,9",
+            "    4: out  rb-3                    ; 204,-3",
+            "    6: rbo  #19                     ; 109,19",
+            "    8: hlt                          ; 99",
+            "    9: db   0, 77, 3",
+        ];
+        assert_eq!(listing.lines().collect::<Vec<&str>>(), expected_listing);
+    }
+}
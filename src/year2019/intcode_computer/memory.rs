@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use super::{IntcodeComputerError, Program, Register, RegisterType};
+
+/// Writes at most this far past the end of the dense part grow it, writes further away go to the sparse part.
+const MAX_DENSE_GAP: usize = 4096;
+
+/// Memory of the Intcode computer. The program and the addresses right after it are kept in a vector, far away
+/// addresses are stored in a map, so that a write to e.g. address 10^12 does not allocate all the memory
+/// before it. Unused memory reads as 0.
+#[derive(Debug, Clone)]
+pub struct Memory<T = RegisterType> {
+    dense: Vec<T>,
+    sparse: BTreeMap<usize, T>,
+    limit: Option<usize>,
+}
+
+impl<T: Register> Memory<T> {
+    pub fn new(program: Program<T>) -> Self {
+        Memory { dense: program, sparse: BTreeMap::new(), limit: None }
+    }
+
+    /// Memory in which only addresses below the `limit` can be accessed.
+    pub fn with_limit(program: Program<T>, limit: usize) -> Result<Self, IntcodeComputerError> {
+        if program.len() > limit {
+            return Err(IntcodeComputerError::MemoryLimitExceeded { address: program.len() - 1, limit });
+        }
+        Ok(Memory { dense: program, sparse: BTreeMap::new(), limit: Some(limit) })
+    }
+
+    /// Number of cells up to the highest written address.
+    pub fn len(&self) -> usize {
+        match self.sparse.keys().next_back() {
+            // The last address is counted as the end of the memory rather than overflowing.
+            Some(address) => address.saturating_add(1),
+            None => self.dense.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at the `address`, unused memory and addresses over the limit read as 0.
+    pub fn read(&self, address: usize) -> T {
+        match self.dense.get(address) {
+            Some(value) => value.clone(),
+            None => self.sparse.get(&address).cloned().unwrap_or_else(T::zero),
+        }
+    }
+
+    /// Same as `read`, but fails for addresses over the limit.
+    pub fn checked_read(&self, address: usize) -> Result<T, IntcodeComputerError> {
+        self.check_limit(address)?;
+        Ok(self.read(address))
+    }
+
+    pub fn write(&mut self, address: usize, value: T) -> Result<(), IntcodeComputerError> {
+        self.check_limit(address)?;
+        if address < self.dense.len() {
+            self.dense[address] = value;
+        } else if address - self.dense.len() <= MAX_DENSE_GAP {
+            let old_len = self.dense.len();
+            self.dense.resize(address + 1, T::zero());
+            // Move the sparse cells the dense part grew over.
+            let moved = self.sparse.range(old_len..=address).map(|(address, _)| *address).collect::<Vec<usize>>();
+            for moved_address in moved {
+                self.dense[moved_address] = self.sparse.remove(&moved_address).unwrap();
+            }
+            self.dense[address] = value;
+        } else {
+            self.sparse.insert(address, value);
+        }
+        Ok(())
+    }
+
+    /// Returns up to `count` cells starting at the `address`, stopping at the end of the used memory.
+    pub fn fetch(&self, address: usize, count: usize) -> Result<Vec<T>, IntcodeComputerError> {
+        let len = self.len();
+        if address >= len {
+            return Err(IntcodeComputerError::IpOutOfMemory { ip: address });
+        }
+        let end = address.checked_add(count)
+            .ok_or_else(|| IntcodeComputerError::new(format!("Memory range {}+{} out of bounds", address, count)))?;
+        Ok((address..len.min(end)).map(|address| self.read(address)).collect())
+    }
+
+    fn check_limit(&self, address: usize) -> Result<(), IntcodeComputerError> {
+        match self.limit {
+            Some(limit) if address >= limit => Err(IntcodeComputerError::MemoryLimitExceeded { address, limit }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_memory() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+        memory.write(1_000_000_000_000, 7).unwrap();
+        memory.write(10_000, 5).unwrap();
+        assert_eq!(memory.dense.len(), 3);
+        assert_eq!(memory.len(), 1_000_000_000_001);
+        assert_eq!(memory.read(1_000_000_000_000), 7);
+        assert_eq!(memory.read(999), 0);
+
+        // Growing the dense part takes over the sparse cells.
+        memory.write(4_000, 1).unwrap();
+        memory.write(8_000, 1).unwrap();
+        memory.write(12_000, 1).unwrap();
+        assert_eq!(memory.dense.len(), 12_001);
+        assert_eq!(memory.sparse.len(), 1);
+        assert_eq!(memory.read(10_000), 5);
+        assert_eq!(memory.fetch(9_999, 4).unwrap(), vec![0, 5, 0, 0]);
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut memory = Memory::with_limit(vec![1, 2, 3], 100).unwrap();
+        assert!(memory.write(99, 1).is_ok());
+        assert!(memory.write(100, 1).is_err());
+        assert!(memory.checked_read(100).is_err());
+        assert!(Memory::with_limit(vec![1, 2, 3], 2).is_err());
+        assert!(memory.fetch(100, 4).is_err());
+
+        let mut memory = Memory::new(vec![1, 2, 3]);
+        memory.write(usize::MAX, 1).unwrap();
+        assert!(memory.fetch(usize::MAX - 2, 4).is_err());
+    }
+}
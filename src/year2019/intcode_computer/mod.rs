@@ -0,0 +1,650 @@
+use std::{error, fmt, str, thread};
+use std::collections::VecDeque;
+use std::sync::{Arc, Barrier};
+
+use num::{CheckedAdd, CheckedMul, FromPrimitive, One, ToPrimitive, Zero};
+
+pub mod ascii;
+pub mod assembler;
+pub mod debugger;
+pub mod devices;
+pub mod disassembler;
+pub mod memory;
+pub mod network;
+pub mod tracer;
+
+use devices::{InputDevice, OutputDevice};
+use memory::Memory;
+use tracer::{TraceRecord, Tracer};
+
+/// Numeric type of the computer's memory cells, e.g. `i64`, `i128` or `num::BigInt`. Arithmetic overflow is
+/// reported as an error.
+pub trait Register: Clone + PartialOrd + fmt::Debug + fmt::Display + str::FromStr + Zero + One
+                    + CheckedAdd + CheckedMul + ToPrimitive + FromPrimitive {}
+
+impl<T> Register for T
+where T: Clone + PartialOrd + fmt::Debug + fmt::Display + str::FromStr + Zero + One
+         + CheckedAdd + CheckedMul + ToPrimitive + FromPrimitive {}
+
+type RegisterType = i64;
+pub type Program<T = RegisterType> = Vec<T>;
+
+/// Opcode and three parameters.
+const MAX_INSTRUCTION_SIZE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntcodeComputerError {
+    UnknownOpcode { ip: usize, opcode: String },
+    UnknownParameterMode { ip: usize, mode: u32 },
+    /// The instruction's parameters run past the end of the memory.
+    TruncatedInstruction { ip: usize },
+    ImmediateOutputParameter { ip: usize },
+    IpOutOfMemory { ip: usize },
+    InvalidAddress { ip: usize, address: String },
+    MemoryLimitExceeded { address: usize, limit: usize },
+    Overflow { ip: usize, operation: String },
+    OutOfInputs,
+    Other(String),
+}
+
+impl fmt::Display for IntcodeComputerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntcodeComputerError::UnknownOpcode { ip, opcode } => write!(f, "Unknown opcode {} at {}", opcode, ip),
+            IntcodeComputerError::UnknownParameterMode { ip, mode } =>
+                write!(f, "Unknown parameter mode {} at {}", mode, ip),
+            IntcodeComputerError::TruncatedInstruction { ip } => write!(f, "Instruction at {} is truncated", ip),
+            IntcodeComputerError::ImmediateOutputParameter { ip } =>
+                write!(f, "Output parameter of the instruction at {} can't be in immediate mode", ip),
+            IntcodeComputerError::IpOutOfMemory { ip } => write!(f, "Instruction pointer {} out of memory", ip),
+            IntcodeComputerError::InvalidAddress { ip, address } =>
+                write!(f, "Invalid address {} used by the instruction at {}", address, ip),
+            IntcodeComputerError::MemoryLimitExceeded { address, limit } =>
+                write!(f, "Address {} is over the memory limit {}", address, limit),
+            IntcodeComputerError::Overflow { ip, operation } => write!(f, "Overflow at {}: {}", ip, operation),
+            IntcodeComputerError::OutOfInputs => write!(f, "Program ran out of inputs"),
+            IntcodeComputerError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for IntcodeComputerError {}
+
+impl IntcodeComputerError {
+    pub fn new(message: String) -> IntcodeComputerError {
+        IntcodeComputerError::Other(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterMode<T = RegisterType> {
+    Position(T),
+    Immediate(T),
+    Relative(T),
+}
+
+impl<T: fmt::Display> fmt::Display for ParameterMode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParameterMode::Position(address) => write!(f, "Position({})", address),
+            ParameterMode::Immediate(value) => write!(f, "Immediate({})", value),
+            ParameterMode::Relative(value) => write!(f, "Relative({})", value),
+        }
+    }
+}
+
+/// Decoded instruction. The last parameter of `Add`, `Mul`, `Lst`, `Eqs` and the parameter of `Inp` is the
+/// memory location the instruction writes to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction<T = RegisterType> {
+    Halt,
+    Add(ParameterMode<T>, ParameterMode<T>, ParameterMode<T>),
+    Mul(ParameterMode<T>, ParameterMode<T>, ParameterMode<T>),
+    Inp(ParameterMode<T>),
+    Out(ParameterMode<T>),
+    Jit(ParameterMode<T>, ParameterMode<T>),
+    Jif(ParameterMode<T>, ParameterMode<T>),
+    Lst(ParameterMode<T>, ParameterMode<T>, ParameterMode<T>),
+    Eqs(ParameterMode<T>, ParameterMode<T>, ParameterMode<T>),
+    Rbo(ParameterMode<T>),
+}
+
+impl<T> Instruction<T> {
+    /// Number of memory cells taken by the instruction, including the opcode.
+    pub fn size(&self) -> usize {
+        self.parameters().len() + 1
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Halt => "hlt",
+            Instruction::Add(_, _, _) => "add",
+            Instruction::Mul(_, _, _) => "mul",
+            Instruction::Inp(_) => "inp",
+            Instruction::Out(_) => "out",
+            Instruction::Jit(_, _) => "jit",
+            Instruction::Jif(_, _) => "jif",
+            Instruction::Lst(_, _, _) => "lst",
+            Instruction::Eqs(_, _, _) => "eqs",
+            Instruction::Rbo(_) => "rbo",
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<&ParameterMode<T>> {
+        match self {
+            Instruction::Halt => vec![],
+            Instruction::Inp(a) | Instruction::Out(a) | Instruction::Rbo(a) => vec![a],
+            Instruction::Jit(a, b) | Instruction::Jif(a, b) => vec![a, b],
+            Instruction::Add(a, b, c) | Instruction::Mul(a, b, c) | Instruction::Lst(a, b, c) | Instruction::Eqs(a, b, c) => vec![a, b, c],
+        }
+    }
+
+    /// Parameters the instruction reads from, i.e. all of them except the written one.
+    pub fn read_parameters(&self) -> Vec<&ParameterMode<T>> {
+        let mut parameters = self.parameters();
+        if self.written_parameter().is_some() {
+            parameters.pop();
+        }
+        parameters
+    }
+
+    /// Parameter the instruction writes its result to.
+    pub fn written_parameter(&self) -> Option<&ParameterMode<T>> {
+        match self {
+            Instruction::Inp(a) | Instruction::Add(_, _, a) | Instruction::Mul(_, _, a)
+                | Instruction::Lst(_, _, a) | Instruction::Eqs(_, _, a) => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// Result of running the `IntcodeHardware` until it can't continue on its own.
+#[derive(Debug, PartialEq)]
+pub enum RunState<T = RegisterType> {
+    /// The program executes an input instruction but the input queue is empty. Running again after providing
+    /// an input continues with the same instruction.
+    NeedsInput,
+    /// The program produced an output value.
+    Output(T),
+    Halted,
+}
+
+/// Threaded wrapper around the `IntcodeHardware`, communicating through its devices, such as `mpsc` channels.
+pub struct IntcodeComputer<T = RegisterType> {
+    thread_handle: Option<thread::JoinHandle<Result<T, IntcodeComputerError>>>,
+    finish_barrier: Option<Arc<Barrier>>,
+}
+
+impl<T: Register + Send + 'static> IntcodeComputer<T> {
+    pub fn new(finish_barrier: Option<Arc<Barrier>>) -> Self {
+        IntcodeComputer { thread_handle: None, finish_barrier }
+    }
+
+    pub fn start(&mut self, program: Program<T>, input: impl InputDevice<T> + Send + 'static,
+                 output: impl OutputDevice<T> + Send + 'static) -> Result<(), IntcodeComputerError> {
+        if self.thread_handle.is_some() {
+            return Err(IntcodeComputerError::new(String::from("Computer already running")));
+        };
+
+        let barrier = self.finish_barrier.as_ref().map(Arc::clone);
+        self.thread_handle = Some(thread::spawn(move|| {
+            let mut hardware = IntcodeHardware::new(program);
+            IntcodeComputer::run_on_devices(&mut hardware, input, output, barrier)
+        }));
+        Ok(())
+    }
+
+    pub fn wait_for_result(&mut self) -> Result<T, IntcodeComputerError> {
+        match self.thread_handle.take() {
+            Some(thread_handle) => {
+                match thread_handle.join() {
+                    Ok(result) => result,
+                    Err(_) => Err(IntcodeComputerError::new(String::from("Could not join thread")))
+                }
+            }
+            None => Err(IntcodeComputerError::new(String::from("Computer is not running")))
+        }
+    }
+
+    fn run_on_devices(hardware: &mut IntcodeHardware<T>, mut input: impl InputDevice<T>, mut output: impl OutputDevice<T>,
+                      finish_barrier: Option<Arc<Barrier>>) -> Result<T, IntcodeComputerError> {
+        if hardware.run_with_devices(&mut input, &mut output)? == RunState::NeedsInput {
+            return Err(IntcodeComputerError::new(String::from("Input not available")));
+        }
+
+        // Wait in case of cooperative computation so that the mpsc::Channel is not droppped prematurely.
+        if let Some(barrier) = &finish_barrier {
+            barrier.wait();
+        }
+
+        Ok(hardware.read_memory(0))
+    }
+}
+
+/// Single-threaded Intcode virtual machine. Inputs are queued with `push_input` and the machine is driven
+/// by `run`, which returns whenever the program needs an input, produces an output or halts.
+pub struct IntcodeHardware<T = RegisterType> {
+    memory: Memory<T>,
+    ip: usize,
+    relative_base: T,
+    inputs: VecDeque<T>,
+}
+
+impl<T: Register> IntcodeHardware<T> {
+    pub fn new(program: Program<T>) -> Self {
+        IntcodeHardware { memory: Memory::new(program), ip: 0, relative_base: T::zero(), inputs: VecDeque::new() }
+    }
+
+    /// Hardware which fails when the program accesses an address at or over the `memory_limit`.
+    pub fn with_memory_limit(program: Program<T>, memory_limit: usize) -> Result<Self, IntcodeComputerError> {
+        Ok(IntcodeHardware { memory: Memory::with_limit(program, memory_limit)?, ip: 0, relative_base: T::zero(),
+                             inputs: VecDeque::new() })
+    }
+
+    pub fn push_input(&mut self, value: T) {
+        self.inputs.push_back(value);
+    }
+
+    /// Returns the value stored at the `address`, unused memory reads as 0.
+    pub fn read_memory(&self, address: usize) -> T {
+        self.memory.read(address)
+    }
+
+    pub fn write_memory(&mut self, address: usize, value: T) -> Result<(), IntcodeComputerError> {
+        self.memory.write(address, value)
+    }
+
+    /// Decodes the instruction stored at the `address`.
+    pub fn instruction_at(&self, address: usize) -> Result<Instruction<T>, IntcodeComputerError> {
+        let words = self.memory.fetch(address, MAX_INSTRUCTION_SIZE)?;
+        decode_instruction(&words, address)
+    }
+
+    /// Address of the next instruction to be executed.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> &T {
+        &self.relative_base
+    }
+
+    /// Runs the program until it needs an input, produces an output or halts.
+    pub fn run(&mut self) -> Result<RunState<T>, IntcodeComputerError> {
+        self.run_traced(None)
+    }
+
+    /// Same as `run`, reporting every executed instruction to the `tracer`.
+    pub fn run_with_tracer(&mut self, tracer: &mut dyn Tracer<T>) -> Result<RunState<T>, IntcodeComputerError> {
+        self.run_traced(Some(tracer))
+    }
+
+    /// Executes a single instruction. Returns a `RunState` if the instruction interrupted the execution.
+    pub fn step(&mut self) -> Result<Option<RunState<T>>, IntcodeComputerError> {
+        self.step_traced(None)
+    }
+
+    /// Same as `step`, reporting the executed instruction to the `tracer`.
+    pub fn step_with_tracer(&mut self, tracer: &mut dyn Tracer<T>) -> Result<Option<RunState<T>>, IntcodeComputerError> {
+        self.step_traced(Some(tracer))
+    }
+
+    /// Runs the program until it halts or needs an input the `input` device doesn't have, which is returned as
+    /// `RunState::NeedsInput`. Inputs queued with `push_input` are read before the ones of the device.
+    pub fn run_with_devices(&mut self, input: &mut dyn InputDevice<T>,
+                            output: &mut dyn OutputDevice<T>) -> Result<RunState<T>, IntcodeComputerError> {
+        loop {
+            match self.run()? {
+                RunState::NeedsInput => match input.read()? {
+                    Some(value) => self.push_input(value),
+                    None => return Ok(RunState::NeedsInput),
+                },
+                RunState::Output(value) => output.write(value)?,
+                RunState::Halted => return Ok(RunState::Halted),
+            }
+        }
+    }
+
+    /// Runs the program to completion with the given inputs and returns all of its outputs.
+    pub fn run_with_inputs(&mut self, inputs: &[T]) -> Result<Vec<T>, IntcodeComputerError> {
+        let mut outputs = vec![];
+        match self.run_with_devices(&mut inputs.iter().cloned().collect::<VecDeque<T>>(), &mut outputs)? {
+            RunState::NeedsInput => Err(IntcodeComputerError::OutOfInputs),
+            _ => Ok(outputs),
+        }
+    }
+
+    fn run_traced(&mut self, mut tracer: Option<&mut dyn Tracer<T>>) -> Result<RunState<T>, IntcodeComputerError> {
+        loop {
+            if let Some(state) = self.step_traced(tracer.as_mut().map(|tracer| &mut **tracer as &mut dyn Tracer<T>))? {
+                return Ok(state);
+            }
+        }
+    }
+
+    fn step_traced(&mut self, tracer: Option<&mut dyn Tracer<T>>) -> Result<Option<RunState<T>>, IntcodeComputerError> {
+        let instruction = self.instruction_at(self.ip)?;
+        let tracer = match tracer {
+            Some(tracer) => tracer,
+            None => return self.execute_instruction(&instruction),
+        };
+
+        let (ip, relative_base) = (self.ip, self.relative_base.clone());
+        let operands = instruction.read_parameters().into_iter().map(|parameter| self.load_parameter(parameter))
+            .collect::<Result<Vec<T>, IntcodeComputerError>>()?;
+        let write_address = instruction.written_parameter().map(|parameter| self.resolve_address(parameter)).transpose()?;
+
+        let state = self.execute_instruction(&instruction)?;
+        // The input instruction is not executed until there is an input.
+        if state == Some(RunState::NeedsInput) {
+            return Ok(state);
+        }
+
+        let memory_write = write_address.map(|address| (address, self.read_memory(address)));
+        tracer.trace(&TraceRecord { ip, relative_base, instruction, operands, memory_write })?;
+        Ok(state)
+    }
+
+    fn execute_instruction(&mut self, instruction: &Instruction<T>) -> Result<Option<RunState<T>>, IntcodeComputerError> {
+        match instruction {
+            Instruction::Halt => {
+                return Ok(Some(RunState::Halted))
+            }
+            Instruction::Add(parameter_a, parameter_b, address) => {
+                let (value_a, value_b) = (self.load_parameter(parameter_a)?, self.load_parameter(parameter_b)?);
+                let value = value_a.checked_add(&value_b).ok_or_else(|| self.overflow_error(&value_a, "+", &value_b))?;
+                self.store_value(value, address)?;
+                self.ip += 4;
+            },
+            Instruction::Mul(parameter_a, parameter_b, address) => {
+                let (value_a, value_b) = (self.load_parameter(parameter_a)?, self.load_parameter(parameter_b)?);
+                let value = value_a.checked_mul(&value_b).ok_or_else(|| self.overflow_error(&value_a, "*", &value_b))?;
+                self.store_value(value, address)?;
+                self.ip += 4;
+            },
+            Instruction::Inp(address) => {
+                let value = match self.inputs.pop_front() {
+                    Some(value) => value,
+                    None => return Ok(Some(RunState::NeedsInput)),
+                };
+                self.store_value(value, address)?;
+                self.ip += 2;
+            },
+            Instruction::Out(parameter) => {
+                let value = self.load_parameter(parameter)?;
+                self.ip += 2;
+                return Ok(Some(RunState::Output(value)))
+            },
+            Instruction::Jit(parameter, address) => {
+                let value = self.load_parameter(parameter)?;
+                let address = self.load_parameter(address)?;
+                if !value.is_zero() {
+                    self.ip = to_address(&address, self.ip)?;
+                } else {
+                    self.ip += 3;
+                }
+            },
+            Instruction::Jif(parameter, address) => {
+                let value = self.load_parameter(parameter)?;
+                let address = self.load_parameter(address)?;
+                if value.is_zero() {
+                    self.ip = to_address(&address, self.ip)?;
+                } else {
+                    self.ip += 3;
+                }
+            },
+            Instruction::Lst(parameter_a, parameter_b, address) => {
+                let value = match self.load_parameter(parameter_a)? < self.load_parameter(parameter_b)? {
+                    true => T::one(),
+                    false => T::zero(),
+                };
+                self.store_value(value, address)?;
+                self.ip += 4;
+            },
+            Instruction::Eqs(parameter_a, parameter_b, address) => {
+                let value = match self.load_parameter(parameter_a)? == self.load_parameter(parameter_b)? {
+                    true => T::one(),
+                    false => T::zero(),
+                };
+                self.store_value(value, address)?;
+                self.ip += 4;
+            },
+            Instruction::Rbo(parameter) => {
+                let value = self.load_parameter(parameter)?;
+                self.relative_base = self.relative_base.checked_add(&value)
+                    .ok_or_else(|| self.overflow_error(&self.relative_base, "+", &value))?;
+                self.ip += 2
+            },
+        };
+        Ok(None)
+    }
+
+    fn load_parameter(&self, parameter: &ParameterMode<T>) -> Result<T, IntcodeComputerError> {
+        match parameter {
+            ParameterMode::Immediate(value) => Ok(value.clone()),
+            parameter => self.memory.checked_read(self.resolve_address(parameter)?),
+        }
+    }
+
+    fn resolve_address(&self, parameter: &ParameterMode<T>) -> Result<usize, IntcodeComputerError> {
+        match parameter {
+            ParameterMode::Position(address) => to_address(address, self.ip),
+            ParameterMode::Relative(value) => {
+                let address = self.relative_base.checked_add(value)
+                    .ok_or_else(|| self.overflow_error(&self.relative_base, "+", value))?;
+                to_address(&address, self.ip)
+            },
+            ParameterMode::Immediate(_) => Err(IntcodeComputerError::ImmediateOutputParameter { ip: self.ip }),
+        }
+    }
+
+    fn store_value(&mut self, value: T, parameter: &ParameterMode<T>) -> Result<(), IntcodeComputerError> {
+        let address = self.resolve_address(parameter)?;
+        self.write_memory(address, value)
+    }
+
+    fn overflow_error(&self, value_a: &T, operation: &str, value_b: &T) -> IntcodeComputerError {
+        IntcodeComputerError::Overflow { ip: self.ip, operation: format!("{} {} {}", value_a, operation, value_b) }
+    }
+}
+
+/// Converts the `value` used by the instruction at the `ip` into an address. Negative values are invalid.
+fn to_address<T: Register>(value: &T, ip: usize) -> Result<usize, IntcodeComputerError> {
+    value.to_usize().ok_or_else(|| IntcodeComputerError::InvalidAddress { ip, address: value.to_string() })
+}
+
+/// Decodes the instruction whose `words` start at the `ip`. Parameters of the instruction have to fit into the
+/// `words`.
+fn decode_instruction<T: Register>(words: &[T], ip: usize) -> Result<Instruction<T>, IntcodeComputerError> {
+    let opcode = words.first().ok_or(IntcodeComputerError::IpOutOfMemory { ip })?.to_string();
+    let instruction_code = match opcode.len() {
+        1 => format!("0{}", opcode),
+        _ => opcode[(opcode.len() - 2)..opcode.len()].to_owned()
+    };
+    let instruction_code = instruction_code.parse::<u8>()
+        .map_err(|_| IntcodeComputerError::UnknownOpcode { ip, opcode: opcode.clone() })?;
+    let parameter_modes = match opcode.len() {
+        0..=2 => Vec::new(),
+        non_default_modes => {
+            let mut parameter_modes = opcode[..non_default_modes - 2].chars().collect::<Vec<char>>();
+            parameter_modes.reverse();
+            parameter_modes
+        }
+    };
+
+    match instruction_code {
+        99 => {
+            Ok(Instruction::Halt)
+        },
+        1 => {
+            let first_parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let second_parameter = decode_parameter(words, ip, 2, &parameter_modes)?;
+            let result_address = decode_address(words, ip, 3, &parameter_modes)?;
+            Ok(Instruction::Add(first_parameter, second_parameter, result_address))
+        },
+        2 => {
+            let first_parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let second_parameter = decode_parameter(words, ip, 2, &parameter_modes)?;
+            let result_address = decode_address(words, ip, 3, &parameter_modes)?;
+            Ok(Instruction::Mul(first_parameter, second_parameter, result_address))
+        },
+        3 => {
+            let result_address = decode_address(words, ip, 1, &parameter_modes)?;
+            Ok(Instruction::Inp(result_address))
+        },
+        4 => {
+            let parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            Ok(Instruction::Out(parameter))
+        },
+        5 => {
+            let parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let result_address = decode_parameter(words, ip, 2, &parameter_modes)?;
+            Ok(Instruction::Jit(parameter, result_address))
+        }
+        6 => {
+            let parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let result_address = decode_parameter(words, ip, 2, &parameter_modes)?;
+            Ok(Instruction::Jif(parameter, result_address))
+        },
+        7 => {
+            let first_parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let second_parameter = decode_parameter(words, ip, 2, &parameter_modes)?;
+            let result_address = decode_address(words, ip, 3, &parameter_modes)?;
+            Ok(Instruction::Lst(first_parameter, second_parameter, result_address))
+        },
+        8 => {
+            let first_parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            let second_parameter = decode_parameter(words, ip, 2, &parameter_modes)?;
+            let result_address = decode_address(words, ip, 3, &parameter_modes)?;
+            Ok(Instruction::Eqs(first_parameter, second_parameter, result_address))
+        },
+        9 => {
+            let parameter = decode_parameter(words, ip, 1, &parameter_modes)?;
+            Ok(Instruction::Rbo(parameter))
+        },
+        _ => Err(IntcodeComputerError::UnknownOpcode { ip, opcode })
+    }
+}
+
+fn decode_parameter<T: Register>(words: &[T], ip: usize, parameter_position: usize,
+                                 parameter_modes: &[char]) -> Result<ParameterMode<T>, IntcodeComputerError> {
+    let parameter = words.get(parameter_position).cloned().ok_or(IntcodeComputerError::TruncatedInstruction { ip })?;
+    match parameter_modes.get(parameter_position - 1).and_then(|x| char::to_digit(*x, 10)).unwrap_or(0) {
+        0 => Ok(ParameterMode::Position(parameter)),
+        1 => Ok(ParameterMode::Immediate(parameter)),
+        2 => Ok(ParameterMode::Relative(parameter)),
+        mode => Err(IntcodeComputerError::UnknownParameterMode { ip, mode })
+    }
+}
+
+/// Decodes a parameter the instruction writes to, which can't be in the immediate mode.
+fn decode_address<T: Register>(words: &[T], ip: usize, parameter_position: usize,
+                               parameter_modes: &[char]) -> Result<ParameterMode<T>, IntcodeComputerError> {
+    match decode_parameter(words, ip, parameter_position, parameter_modes)? {
+        ParameterMode::Immediate(_) => Err(IntcodeComputerError::ImmediateOutputParameter { ip }),
+        parameter => Ok(parameter),
+    }
+}
+
+pub mod utils {
+    use std::fmt::Display;
+
+    use crate::aoc_error::AocError;
+    use crate::utils::input_conversion;
+    use super::*;
+
+    /// Parses the first line of the text as the comma separated values of the program. Invalid values are reported
+    /// with their line and column.
+    pub fn parse_intcode_program<T: Register>(program_as_string: &str) -> Result<Program<T>, AocError>
+    where T::Err: Display {
+        input_conversion::comma_separated(program_as_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+    use devices::{ChannelInput, ChannelOutput};
+
+    #[test]
+    fn test_run_states() {
+        // Echoes a single input and halts.
+        let mut computer = IntcodeHardware::new(vec![3, 0, 4, 0, 99]);
+        assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+        assert_eq!(computer.run().unwrap(), RunState::NeedsInput);
+        computer.push_input(42);
+        assert_eq!(computer.run().unwrap(), RunState::Output(42));
+        assert_eq!(computer.run().unwrap(), RunState::Halted);
+        assert_eq!(computer.run().unwrap(), RunState::Halted);
+    }
+
+    #[test]
+    fn test_run_with_inputs() {
+        let mut computer = IntcodeHardware::new(vec![3, 0, 4, 0, 99]);
+        assert_eq!(computer.run_with_inputs(&[7]).unwrap(), vec![7]);
+        assert_eq!(computer.read_memory(0), 7);
+
+        let mut computer = IntcodeHardware::new(vec![3, 0, 4, 0, 99]);
+        assert!(computer.run_with_inputs(&[]).is_err());
+    }
+
+    #[test]
+    fn test_threaded_computers() {
+        // Two echoing computers chained through channels.
+        let (first_sender, first_receiver) = mpsc::channel();
+        let (second_sender, second_receiver) = mpsc::channel();
+        let (output_sender, output_receiver) = mpsc::channel();
+        let barrier = Arc::new(Barrier::new(2));
+        let mut first = IntcodeComputer::new(Some(Arc::clone(&barrier)));
+        let mut second = IntcodeComputer::new(Some(barrier));
+        first.start(vec![3, 0, 4, 0, 99], ChannelInput::new(first_receiver), ChannelOutput::new(vec![second_sender]))
+            .unwrap();
+        second.start(vec![3, 0, 4, 0, 99], ChannelInput::new(second_receiver), ChannelOutput::new(vec![output_sender]))
+            .unwrap();
+        assert!(second.start(vec![99], VecDeque::new(), vec![]).is_err());
+
+        first_sender.send(5).unwrap();
+        assert_eq!(first.wait_for_result().unwrap(), 5);
+        assert_eq!(second.wait_for_result().unwrap(), 5);
+        assert_eq!(output_receiver.recv().unwrap(), 5);
+        assert!(first.wait_for_result().is_err());
+    }
+
+    #[test]
+    fn test_register_types() {
+        // Squares the input.
+        let program = "3,9,2,9,9,9,4,9,99,0";
+        let mut computer = IntcodeHardware::<i128>::new(utils::parse_intcode_program(program).unwrap());
+        assert_eq!(computer.run_with_inputs(&[1 << 40]).unwrap(), vec![1 << 80]);
+
+        let big_value: num::BigInt = num::BigInt::from(1u8) << 100usize;
+        let mut computer = IntcodeHardware::<num::BigInt>::new(utils::parse_intcode_program(program).unwrap());
+        assert_eq!(computer.run_with_inputs(std::slice::from_ref(&big_value)).unwrap(), vec![&big_value * &big_value]);
+
+        let mut computer = IntcodeHardware::<i32>::new(utils::parse_intcode_program(program).unwrap());
+        assert!(computer.run_with_inputs(&[1 << 16]).is_err());
+        let mut computer = IntcodeHardware::<i64>::new(utils::parse_intcode_program(program).unwrap());
+        assert_eq!(computer.run_with_inputs(&[1 << 16]).unwrap(), vec![1 << 32]);
+
+        let error = utils::parse_intcode_program::<i64>("1,2,\n3,x,4").unwrap_err();
+        assert!(matches!(error, crate::aoc_error::AocError::Parse { line: Some(1), column: Some(5), .. }));
+    }
+
+    #[test]
+    fn test_memory_errors() {
+        // Writes far away, reads the value back and outputs it.
+        let mut computer = IntcodeHardware::<i64>::new(vec![1101, 7, 0, 1_000_000_000_000, 4, 1_000_000_000_000, 99]);
+        assert_eq!(computer.run_with_inputs(&[]).unwrap(), vec![7]);
+
+        let mut computer = IntcodeHardware::<i64>::with_memory_limit(vec![1101, 7, 0, 1_000_000_000_000, 99], 1000).unwrap();
+        assert_eq!(computer.run(), Err(IntcodeComputerError::MemoryLimitExceeded { address: 1_000_000_000_000, limit: 1000 }));
+        assert_eq!(IntcodeHardware::new(vec![4, -1, 99]).run(),
+                   Err(IntcodeComputerError::InvalidAddress { ip: 0, address: String::from("-1") }));
+        assert_eq!(IntcodeHardware::new(vec![1105, 1, 100]).run(), Err(IntcodeComputerError::IpOutOfMemory { ip: 100 }));
+        assert_eq!(IntcodeHardware::new(vec![1001, 0]).run(), Err(IntcodeComputerError::TruncatedInstruction { ip: 0 }));
+        assert_eq!(IntcodeHardware::new(vec![1, 0, 0, 0, 42]).run(),
+                   Err(IntcodeComputerError::UnknownOpcode { ip: 4, opcode: String::from("42") }));
+    }
+}
@@ -0,0 +1,317 @@
+//! Several Intcode machines connected into a network. The machines run one after another in a fixed order on the
+//! current thread, so the simulation is deterministic: a round runs every machine until it halts or waits for an input
+//! it doesn't have, and the outputs are delivered as soon as the machine stops.
+
+use std::collections::VecDeque;
+use std::mem;
+
+use super::devices::InputDevice;
+use super::{IntcodeComputerError, IntcodeHardware, Program, Register, RegisterType, RunState};
+
+/// Address of the NAT in the packet network.
+pub const NAT_ADDRESS: usize = 255;
+
+/// How the outputs of the machines are delivered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Topology {
+    /// Outputs go to the next machine, outputs of the last one leave the network.
+    Pipeline,
+    /// Outputs go to the next machine, the last one feeding the first. Outputs of the last machine are also recorded
+    /// as the outputs of the network.
+    Ring,
+    /// Outputs go to all the other machines and leave the network.
+    Broadcast,
+    /// Outputs are `(destination, x, y)` packets delivered to the machine with the destination address, which is its
+    /// index. Packets for the `NAT_ADDRESS` are kept by the NAT, which sends the last one to the machine 0 whenever
+    /// the whole network is idle. Machines reading an input when they have no packets get -1.
+    Packets,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkEvent<T = RegisterType> {
+    /// A value left the network.
+    Output(T),
+    /// The NAT received a packet.
+    NatReceived { x: T, y: T },
+    /// The network was idle and the NAT sent its packet to the machine 0.
+    NatSent { x: T, y: T },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkState<T = RegisterType> {
+    /// All the machines halted.
+    Halted,
+    /// The machines which didn't halt wait for inputs which nobody will send. In the packet network, all the machines
+    /// are idle and the NAT has nothing to send.
+    Deadlocked,
+    /// The stop condition accepted the event.
+    Stopped(NetworkEvent<T>),
+}
+
+struct Machine<T> {
+    hardware: IntcodeHardware<T>,
+    inbox: VecDeque<T>,
+    /// Outputs which don't form a complete packet yet.
+    partial_packet: Vec<T>,
+    halted: bool,
+}
+
+/// Input device of a machine for a single run. Counts the values read from the inbox, and in the packet network
+/// gives -1 once when the inbox is empty.
+struct Receiver<'a, T> {
+    inbox: &'a mut VecDeque<T>,
+    no_packet: Option<T>,
+    received: usize,
+}
+
+impl<'a, T> InputDevice<T> for Receiver<'a, T> {
+    fn read(&mut self) -> Result<Option<T>, IntcodeComputerError> {
+        match self.inbox.pop_front() {
+            Some(value) => {
+                self.received += 1;
+                Ok(Some(value))
+            },
+            None => Ok(self.no_packet.take()),
+        }
+    }
+}
+
+pub struct Network<T = RegisterType> {
+    machines: Vec<Machine<T>>,
+    topology: Topology,
+    outputs: Vec<T>,
+    /// Last packet received by the NAT.
+    nat_packet: Option<(T, T)>,
+}
+
+impl<T: Register> Network<T> {
+    pub fn new(programs: Vec<Program<T>>, topology: Topology) -> Self {
+        let machines = programs.into_iter().map(|program| Machine {
+            hardware: IntcodeHardware::new(program), inbox: VecDeque::new(), partial_packet: vec![], halted: false,
+        }).collect();
+        Network { machines, topology, outputs: vec![], nat_packet: None }
+    }
+
+    /// Network of `count` machines running the same program.
+    pub fn with_copies(program: &Program<T>, count: usize, topology: Topology) -> Self {
+        Network::new(vec![program.clone(); count], topology)
+    }
+
+    /// Queues the `value` as an input of the `machine`, e.g. its configuration or address.
+    pub fn send(&mut self, machine: usize, value: T) -> Result<(), IntcodeComputerError> {
+        let count = self.machines.len();
+        self.machines.get_mut(machine)
+            .ok_or_else(|| IntcodeComputerError::new(format!("No machine {} in a network of {}", machine, count)))?
+            .inbox.push_back(value);
+        Ok(())
+    }
+
+    /// Values which left the network, in the order they were output.
+    pub fn outputs(&self) -> &[T] {
+        &self.outputs
+    }
+
+    /// Runs the network until it halts or deadlocks.
+    pub fn run(&mut self) -> Result<NetworkState<T>, IntcodeComputerError> {
+        self.run_until(|_| false)
+    }
+
+    /// Runs the network until it halts or deadlocks, or until the `stop` condition returns true for an event.
+    pub fn run_until(&mut self, mut stop: impl FnMut(&NetworkEvent<T>) -> bool) -> Result<NetworkState<T>, IntcodeComputerError> {
+        loop {
+            if self.machines.iter().all(|machine| machine.halted) {
+                return Ok(NetworkState::Halted);
+            }
+
+            let mut is_active = false;
+            for index in 0..self.machines.len() {
+                let (outputs, machine_active) = self.run_machine(index)?;
+                is_active |= machine_active;
+                for event in self.deliver(index, outputs)? {
+                    if stop(&event) {
+                        return Ok(NetworkState::Stopped(event));
+                    }
+                }
+            }
+            if is_active {
+                continue;
+            }
+
+            match self.nat_packet.clone() {
+                Some((x, y)) if self.topology == Topology::Packets && !self.machines[0].halted => {
+                    self.machines[0].inbox.extend(vec![x.clone(), y.clone()]);
+                    let event = NetworkEvent::NatSent { x, y };
+                    if stop(&event) {
+                        return Ok(NetworkState::Stopped(event));
+                    }
+                },
+                _ => return Ok(NetworkState::Deadlocked),
+            }
+        }
+    }
+
+    /// Runs the machine until it halts or waits for an input. Returns its outputs and whether it did anything other
+    /// than waiting, which is reading or writing a value or halting.
+    fn run_machine(&mut self, index: usize) -> Result<(Vec<T>, bool), IntcodeComputerError> {
+        let no_packet = match self.topology {
+            Topology::Packets => Some(T::from_i64(-1)
+                .ok_or_else(|| IntcodeComputerError::new(String::from("The register type can't represent -1")))?),
+            _ => None,
+        };
+        let machine = &mut self.machines[index];
+        if machine.halted {
+            return Ok((vec![], false));
+        }
+
+        let mut receiver = Receiver { inbox: &mut machine.inbox, no_packet, received: 0 };
+        let mut outputs = vec![];
+        let state = machine.hardware.run_with_devices(&mut receiver, &mut outputs)?;
+        let is_active = receiver.received > 0 || !outputs.is_empty() || state == RunState::Halted;
+        machine.halted = state == RunState::Halted;
+        Ok((outputs, is_active))
+    }
+
+    /// Delivers the outputs of the machine with the `index` according to the topology.
+    fn deliver(&mut self, index: usize, outputs: Vec<T>) -> Result<Vec<NetworkEvent<T>>, IntcodeComputerError> {
+        let count = self.machines.len();
+        let mut events = vec![];
+        match self.topology {
+            Topology::Pipeline | Topology::Ring => {
+                let is_last = index + 1 == count;
+                if is_last {
+                    self.outputs.extend(outputs.iter().cloned());
+                    events.extend(outputs.iter().cloned().map(NetworkEvent::Output));
+                }
+                if !is_last || self.topology == Topology::Ring {
+                    self.machines[(index + 1) % count].inbox.extend(outputs);
+                }
+            },
+            Topology::Broadcast => {
+                for (_, machine) in self.machines.iter_mut().enumerate().filter(|(other, _)| *other != index) {
+                    machine.inbox.extend(outputs.iter().cloned());
+                }
+                self.outputs.extend(outputs.iter().cloned());
+                events.extend(outputs.into_iter().map(NetworkEvent::Output));
+            },
+            Topology::Packets => {
+                let mut values = mem::take(&mut self.machines[index].partial_packet);
+                values.extend(outputs);
+                let mut chunks = values.chunks_exact(3);
+                for packet in &mut chunks {
+                    if let Some(event) = self.deliver_packet(index, &packet[0], packet[1].clone(), packet[2].clone())? {
+                        events.push(event);
+                    }
+                }
+                self.machines[index].partial_packet = chunks.remainder().to_vec();
+            },
+        }
+        Ok(events)
+    }
+
+    fn deliver_packet(&mut self, sender: usize, destination: &T, x: T, y: T) -> Result<Option<NetworkEvent<T>>, IntcodeComputerError> {
+        match destination.to_usize() {
+            Some(NAT_ADDRESS) => {
+                self.nat_packet = Some((x.clone(), y.clone()));
+                Ok(Some(NetworkEvent::NatReceived { x, y }))
+            },
+            Some(address) if address < self.machines.len() => {
+                self.machines[address].inbox.extend(vec![x, y]);
+                Ok(None)
+            },
+            _ => Err(IntcodeComputerError::new(format!("Machine {} sent a packet to the unknown address {}", sender, destination))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::assembler;
+
+    /// Adds every input to the configuration read first and outputs the sum, halts once the sum reaches 20.
+    const ADDER: &str = "
+                inp  [config]
+        loop:   inp  [value]
+                add  [value], [config], [value]
+                out  [value]
+                lst  [value], #20, [flag]
+                jit  [flag], #loop
+                hlt
+        config: db   0
+        value:  db   0
+        flag:   db   0";
+
+    fn configured_network(topology: Topology, configurations: Vec<i64>) -> Network {
+        let mut network = Network::with_copies(&assembler::assemble(ADDER).unwrap(), configurations.len(), topology);
+        for (machine, configuration) in configurations.into_iter().enumerate() {
+            network.send(machine, configuration).unwrap();
+        }
+        network.send(0, 0).unwrap();
+        network
+    }
+
+    #[test]
+    fn test_pipeline_and_ring() {
+        let mut pipeline = configured_network(Topology::Pipeline, vec![1, 2, 3]);
+        assert_eq!(pipeline.run().unwrap(), NetworkState::Deadlocked);
+        assert_eq!(pipeline.outputs(), &[6]);
+
+        let mut ring = configured_network(Topology::Ring, vec![1, 2, 3]);
+        assert_eq!(ring.run().unwrap(), NetworkState::Halted);
+        assert_eq!(ring.outputs(), &[6, 12, 18, 24]);
+
+        let mut ring = configured_network(Topology::Ring, vec![1, 2, 3]);
+        assert_eq!(ring.run_until(|event| *event == NetworkEvent::Output(12)).unwrap(),
+                   NetworkState::Stopped(NetworkEvent::Output(12)));
+        assert!(ring.send(3, 1).is_err());
+
+        let mut broadcast = configured_network(Topology::Broadcast, vec![8, 9, 10]);
+        assert_eq!(broadcast.run().unwrap(), NetworkState::Halted);
+        assert_eq!(broadcast.outputs(), &[8, 17, 18, 27, 25, 27]);
+    }
+
+    #[test]
+    fn test_packets() {
+        // Sends a packet with its address to the next machine, the last machine sends to the NAT. Received packets
+        // are passed on the same way, with y increased by 1.
+        let source = "
+                    inp  [address]
+                    add  [address], #1, [next]
+                    eqs  [next], #2, [flag]
+                    jif  [flag], #send
+                    add  #255, #0, [next]
+            send:   out  [next]
+                    out  [address]
+                    out  #7
+            receive: inp [x]
+                    eqs  [x], #-1, [flag]
+                    jit  [flag], #receive
+                    inp  [y]
+                    add  [y], #1, [y]
+                    out  [next]
+                    out  [x]
+                    out  [y]
+                    jit  #1, #receive
+            address: db  0
+            next:   db   0
+            flag:   db   0
+            x:      db   0
+            y:      db   0";
+        let mut network = Network::with_copies(&assembler::assemble(source).unwrap(), 2, Topology::Packets);
+        network.send(0, 0).unwrap();
+        network.send(1, 1).unwrap();
+
+        let mut events = vec![];
+        let state = network.run_until(|event| {
+            events.push(event.clone());
+            *event == NetworkEvent::NatSent { x: 0, y: 12 }
+        }).unwrap();
+        assert_eq!(state, NetworkState::Stopped(NetworkEvent::NatSent { x: 0, y: 12 }));
+        assert_eq!(events, vec![
+            NetworkEvent::NatReceived { x: 1, y: 7 }, NetworkEvent::NatReceived { x: 0, y: 8 },
+            NetworkEvent::NatSent { x: 0, y: 8 }, NetworkEvent::NatReceived { x: 0, y: 10 },
+            NetworkEvent::NatSent { x: 0, y: 10 }, NetworkEvent::NatReceived { x: 0, y: 12 },
+            NetworkEvent::NatSent { x: 0, y: 12 },
+        ]);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+use super::{disassembler, Instruction, IntcodeComputerError, Register, RegisterType};
+
+/// Record of a single executed instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord<T = RegisterType> {
+    pub ip: usize,
+    /// Relative base before the instruction was executed.
+    pub relative_base: T,
+    pub instruction: Instruction<T>,
+    /// Values of the parameters the instruction reads, in order.
+    pub operands: Vec<T>,
+    /// Address and value written by the instruction.
+    pub memory_write: Option<(usize, T)>,
+}
+
+/// Sink for the instructions executed by `IntcodeHardware::run_with_tracer`.
+pub trait Tracer<T = RegisterType> {
+    fn trace(&mut self, record: &TraceRecord<T>) -> Result<(), IntcodeComputerError>;
+}
+
+/// Keeps all the trace records in memory.
+pub struct TraceRecorder<T = RegisterType> {
+    pub records: Vec<TraceRecord<T>>,
+}
+
+impl<T> Default for TraceRecorder<T> {
+    fn default() -> Self {
+        TraceRecorder { records: vec![] }
+    }
+}
+
+impl<T: Clone> Tracer<T> for TraceRecorder<T> {
+    fn trace(&mut self, record: &TraceRecord<T>) -> Result<(), IntcodeComputerError> {
+        self.records.push(record.clone());
+        Ok(())
+    }
+}
+
+/// Writes a line per executed instruction, in a format suitable for diffing traces:
+///
+/// ```text
+///    27: eqs  [1000], #1, [63]        ; 1, 1 -> [63] = 1
+/// ```
+pub struct TraceWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(writer: W) -> Self {
+        TraceWriter { writer }
+    }
+}
+
+impl<W: Write, T: Register> Tracer<T> for TraceWriter<W> {
+    fn trace(&mut self, record: &TraceRecord<T>) -> Result<(), IntcodeComputerError> {
+        let mut line = format!("{:>5}: {:<28} ;", record.ip, disassembler::format_instruction(&record.instruction));
+        if !record.operands.is_empty() {
+            let operands = record.operands.iter().map(T::to_string).collect::<Vec<String>>();
+            write!(line, " {}", operands.join(", ")).unwrap();
+        }
+        if let Some((address, value)) = &record.memory_write {
+            write!(line, " -> [{}] = {}", address, value).unwrap();
+        }
+        writeln!(self.writer, "{}", line)
+            .map_err(|error| IntcodeComputerError::new(format!("Could not write the trace: {}", error)))
+    }
+}
+
+/// Counts executed instructions per mnemonic and per address.
+pub struct Profiler<T = RegisterType> {
+    total_count: u64,
+    mnemonic_counts: HashMap<&'static str, u64>,
+    address_counts: HashMap<usize, (u64, Instruction<T>)>,
+}
+
+impl<T> Default for Profiler<T> {
+    fn default() -> Self {
+        Profiler { total_count: 0, mnemonic_counts: HashMap::new(), address_counts: HashMap::new() }
+    }
+}
+
+impl<T: Clone> Tracer<T> for Profiler<T> {
+    fn trace(&mut self, record: &TraceRecord<T>) -> Result<(), IntcodeComputerError> {
+        self.total_count += 1;
+        *self.mnemonic_counts.entry(record.instruction.mnemonic()).or_insert(0) += 1;
+        self.address_counts.entry(record.ip).or_insert_with(|| (0, record.instruction.clone())).0 += 1;
+        Ok(())
+    }
+}
+
+impl<T: Register> Profiler<T> {
+    pub fn total_count(&self) -> u64 {
+        self.total_count
+    }
+
+    /// Returns (mnemonic, count) pairs ordered from the most executed.
+    pub fn mnemonic_counts(&self) -> Vec<(&'static str, u64)> {
+        let mut counts = self.mnemonic_counts.iter().map(|(mnemonic, count)| (*mnemonic, *count)).collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
+    /// Returns (address, count) pairs of the `limit` most executed addresses, i.e. the hot loops.
+    pub fn hot_addresses(&self, limit: usize) -> Vec<(usize, u64)> {
+        let mut counts = self.address_counts.iter().map(|(address, (count, _))| (*address, *count)).collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts.truncate(limit);
+        counts
+    }
+
+    /// Human-readable summary with instruction counts and the `num_hot_addresses` most executed addresses.
+    pub fn report(&self, num_hot_addresses: usize) -> String {
+        let mut report = format!("Executed instructions: {}\n\nPer instruction:\n", self.total_count);
+        for (mnemonic, count) in self.mnemonic_counts() {
+            writeln!(report, "    {:<4} {:>12} {:>6.2}%", mnemonic, count, self.percentage(count)).unwrap();
+        }
+        writeln!(report, "\nHot addresses:").unwrap();
+        for (address, count) in self.hot_addresses(num_hot_addresses) {
+            let instruction = disassembler::format_instruction(&self.address_counts[&address].1);
+            writeln!(report, "    {:>5}: {:<28} {:>12} {:>6.2}%", address, instruction, count, self.percentage(count)).unwrap();
+        }
+        report
+    }
+
+    fn percentage(&self, count: u64) -> f64 {
+        100. * count as f64 / self.total_count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{assembler, IntcodeHardware, ParameterMode, RunState};
+
+    fn countdown_program() -> Vec<RegisterType> {
+        assembler::assemble("
+            start:  out  [counter]
+                    add  [counter], #-1, [counter]
+                    jit  [counter], #start
+                    hlt
+            counter: db  3").unwrap()
+    }
+
+    #[test]
+    fn test_trace_records() {
+        let mut hardware = IntcodeHardware::new(countdown_program());
+        let mut recorder = TraceRecorder::default();
+        assert_eq!(hardware.run_with_tracer(&mut recorder).unwrap(), RunState::Output(3));
+        assert_eq!(hardware.run_with_tracer(&mut recorder).unwrap(), RunState::Output(2));
+
+        assert_eq!(recorder.records.len(), 4);
+        assert_eq!(recorder.records[1], TraceRecord {
+            ip: 2, relative_base: 0,
+            instruction: Instruction::Add(ParameterMode::Position(10), ParameterMode::Immediate(-1), ParameterMode::Position(10)),
+            operands: vec![3, -1], memory_write: Some((10, 2)),
+        });
+    }
+
+    #[test]
+    fn test_trace_writer() {
+        let mut hardware = IntcodeHardware::new(countdown_program());
+        let mut output = vec![];
+        hardware.run_with_tracer(&mut TraceWriter::new(&mut output)).unwrap();
+        hardware.step_with_tracer(&mut TraceWriter::new(&mut output)).unwrap();
+        let expected_trace = [
+            "    0: out  [10]                    ; 3",
+            "    2: add  [10], #-1, [10]         ; 3, -1 -> [10] = 2",
+        ];
+        assert_eq!(String::from_utf8(output).unwrap().lines().collect::<Vec<&str>>(), expected_trace);
+    }
+
+    #[test]
+    fn test_profiler() {
+        let mut hardware = IntcodeHardware::new(countdown_program());
+        let mut profiler = Profiler::default();
+        while hardware.run_with_tracer(&mut profiler).unwrap() != RunState::Halted {}
+
+        assert_eq!(profiler.total_count(), 10);
+        assert_eq!(profiler.mnemonic_counts(), vec![("add", 3), ("jit", 3), ("out", 3), ("hlt", 1)]);
+        assert_eq!(profiler.hot_addresses(2), vec![(0, 3), (2, 3)]);
+    }
+}
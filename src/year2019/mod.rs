@@ -1,3 +1,6 @@
+use crate::registry::CalendarRegistry;
+use crate::solution::DaySolution;
+
 pub mod calendar;
 pub mod configuration;
 
@@ -16,4 +19,18 @@ pub mod day_12;
 pub mod day_13;
 pub mod day_14;
 
-mod intcode_computer;
\ No newline at end of file
+pub mod intcode_computer;
+
+pub fn register(registry: &mut CalendarRegistry) {
+    registry.register(Box::new(calendar::Calendar2019::new()));
+}
+
+/// Solutions of all the implemented days. A new day needs its module and a line here.
+pub fn solutions() -> Vec<Box<dyn DaySolution>> {
+    vec![
+        Box::new(day_01::Day01), Box::new(day_02::Day02), Box::new(day_03::Day03), Box::new(day_04::Day04),
+        Box::new(day_05::Day05), Box::new(day_06::Day06), Box::new(day_07::Day07), Box::new(day_08::Day08),
+        Box::new(day_09::Day09), Box::new(day_10::Day10), Box::new(day_11::Day11), Box::new(day_12::Day12),
+        Box::new(day_13::Day13), Box::new(day_14::Day14),
+    ]
+}